@@ -26,5 +26,13 @@ bitflags::bitflags! {
         const INTRA_ONLY          = AV_CODEC_CAP_INTRA_ONLY;
         #[cfg(not(feature = "ffmpeg_6_0"))]
         const LOSSLESS            = AV_CODEC_CAP_LOSSLESS;
+        const AVOID_PROBING       = AV_CODEC_CAP_AVOID_PROBING;
+        const HARDWARE            = AV_CODEC_CAP_HARDWARE;
+        const HYBRID              = AV_CODEC_CAP_HYBRID;
+        const ENCODER_FLUSH       = AV_CODEC_CAP_ENCODER_FLUSH;
+        #[cfg(feature = "ffmpeg_6_0")]
+        const ENCODER_REORDERED_OPAQUE = AV_CODEC_CAP_ENCODER_REORDERED_OPAQUE;
+        #[cfg(feature = "ffmpeg_6_0")]
+        const ENCODER_RECON_FRAME = AV_CODEC_CAP_ENCODER_RECON_FRAME;
     }
 }
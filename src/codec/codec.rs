@@ -3,12 +3,15 @@ use std::ptr::NonNull;
 
 use super::config::{FrameRateIter, PixelFormatIter, SampleFormatIter, SampleRateIter};
 use super::descriptor::{CodecDescriptor, CodecDescriptorIter};
+use super::hw_config::HwConfigIter;
 use super::profile::ProfileIter;
 use super::{Capabilities, Id};
 use crate::ffi::*;
 use crate::iters::TerminatedPtrIter;
+use crate::util::format;
 use crate::ChannelLayout;
-use crate::{media, utils};
+use crate::{media, option, utils};
+use libc::c_void;
 
 #[cfg(feature = "ffmpeg_7_1")]
 use crate::codec::config::{ColorRangeIter, ColorSpaceIter, Supported};
@@ -17,6 +20,26 @@ pub fn list_descriptors() -> CodecDescriptorIter {
     CodecDescriptorIter::new()
 }
 
+/// All registered encoders and decoders, narrowed down by the filters of
+/// [`CodecIter`].
+///
+/// ```
+/// use ffmpeg_the_third::codec::{self, Capabilities};
+/// use ffmpeg_the_third::media;
+///
+/// let threaded = codec::list()
+///     .medium(media::Type::Video)
+///     .decoders()
+///     .capabilities(Capabilities::FRAME_THREADS);
+///
+/// for codec in threaded {
+///     println!("{}", codec.name());
+/// }
+/// ```
+pub fn list() -> CodecIter {
+    CodecIter::new()
+}
+
 pub type Audio = Codec<AudioType>;
 pub type Video = Codec<VideoType>;
 pub type Data = Codec<DataType>;
@@ -164,6 +187,24 @@ impl<T> Codec<T> {
         unsafe { Capabilities::from_bits_truncate((*self.as_ptr()).capabilities as u32) }
     }
 
+    /// Name of the external library wrapped by this codec, e.g. `libdav1d`,
+    /// or `None` for codecs native to FFmpeg.
+    pub fn wrapper_name(&self) -> Option<&str> {
+        unsafe { utils::optional_str_from_c_ptr((*self.as_ptr()).wrapper_name) }
+    }
+
+    /// Hardware configurations supported by this codec, in order of
+    /// preference.
+    pub fn hw_configs(&self) -> HwConfigIter<'_> {
+        unsafe { HwConfigIter::new(self.as_ptr()) }
+    }
+
+    /// Class of the private options of the codec, like `preset` or `crf` for
+    /// libx264.
+    pub fn priv_class(&self) -> Option<option::Class<'static>> {
+        unsafe { option::Class::from_raw((*self.as_ptr()).priv_class) }
+    }
+
     pub fn profiles(&self) -> Option<ProfileIter> {
         unsafe {
             if (*self.as_ptr()).profiles.is_null() {
@@ -289,6 +330,153 @@ impl Codec<VideoType> {
     }
 }
 
+/// Iterator over the registered codecs, see [`list`].
+pub struct CodecIter {
+    opaque: *mut c_void,
+
+    medium: Option<media::Type>,
+    encoder: Option<bool>,
+    capabilities: Capabilities,
+    wrapper: Option<Option<String>>,
+    pixel_format: Option<format::Pixel>,
+    sample_format: Option<format::Sample>,
+}
+
+impl CodecIter {
+    pub fn new() -> Self {
+        CodecIter {
+            opaque: std::ptr::null_mut(),
+
+            medium: None,
+            encoder: None,
+            capabilities: Capabilities::empty(),
+            wrapper: None,
+            pixel_format: None,
+            sample_format: None,
+        }
+    }
+
+    /// Only codecs for `medium`.
+    pub fn medium(mut self, medium: media::Type) -> Self {
+        self.medium = Some(medium);
+        self
+    }
+
+    pub fn encoders(mut self) -> Self {
+        self.encoder = Some(true);
+        self
+    }
+
+    pub fn decoders(mut self) -> Self {
+        self.encoder = Some(false);
+        self
+    }
+
+    /// Only codecs with all of `capabilities`.
+    pub fn capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities |= capabilities;
+        self
+    }
+
+    /// Only codecs wrapping the external library `name`, or only the codecs
+    /// native to FFmpeg for `None`.
+    pub fn wrapper(mut self, name: Option<&str>) -> Self {
+        self.wrapper = Some(name.map(String::from));
+        self
+    }
+
+    /// Only video codecs that support `format`.
+    pub fn pixel_format(mut self, format: format::Pixel) -> Self {
+        self.pixel_format = Some(format);
+        self
+    }
+
+    /// Only audio codecs that support `format`.
+    pub fn sample_format(mut self, format: format::Sample) -> Self {
+        self.sample_format = Some(format);
+        self
+    }
+
+    fn matches(&self, codec: Codec) -> bool {
+        if self.medium.is_some_and(|medium| codec.medium() != medium) {
+            return false;
+        }
+
+        if self
+            .encoder
+            .is_some_and(|encoder| codec.is_encoder() != encoder)
+        {
+            return false;
+        }
+
+        if !codec.capabilities().contains(self.capabilities) {
+            return false;
+        }
+
+        if let Some(wrapper) = &self.wrapper {
+            if codec.wrapper_name() != wrapper.as_deref() {
+                return false;
+            }
+        }
+
+        if let Some(format) = self.pixel_format {
+            let Some(codec) = codec.video() else {
+                return false;
+            };
+
+            #[cfg(feature = "ffmpeg_7_1")]
+            let supported = codec.supports_format(format);
+            #[cfg(not(feature = "ffmpeg_7_1"))]
+            let supported = codec
+                .formats()
+                .map_or(true, |mut formats| formats.any(|f| f == format));
+
+            if !supported {
+                return false;
+            }
+        }
+
+        if let Some(format) = self.sample_format {
+            let Some(codec) = codec.audio() else {
+                return false;
+            };
+
+            #[cfg(feature = "ffmpeg_7_1")]
+            let supported = codec.supports_format(format);
+            #[cfg(not(feature = "ffmpeg_7_1"))]
+            let supported = codec
+                .formats()
+                .map_or(true, |mut formats| formats.any(|f| f == format));
+
+            if !supported {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+impl Default for CodecIter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for CodecIter {
+    type Item = Codec;
+
+    fn next(&mut self) -> Option<Codec> {
+        loop {
+            let codec = unsafe { Codec::from_raw(av_codec_iterate(&mut self.opaque))? };
+
+            if self.matches(codec) {
+                return Some(codec);
+            }
+        }
+    }
+}
+
 #[cfg(not(feature = "ffmpeg_7_0"))]
 use crate::ChannelLayoutMask;
 
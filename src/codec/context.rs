@@ -1,14 +1,15 @@
 use std::ptr;
+use std::slice;
 
 use super::decoder::Decoder;
 use super::encoder::Encoder;
-use super::{threading, Compliance, Debug, Flags, Id};
+use super::{extradata, threading, Compliance, Debug, Flags, Id};
 use crate::ffi::*;
 use crate::media;
 use crate::option;
 use crate::{AsMutPtr, AsPtr};
 use crate::{Codec, Error};
-use libc::c_int;
+use libc::{c_int, c_void, EINVAL};
 
 #[cfg(feature = "ffmpeg_8_1")]
 use crate::format::AlphaMode;
@@ -131,6 +132,69 @@ impl Context {
         }
     }
 
+    /// The ASS header (`[Script Info]` and `[V4+ Styles]` sections) of a text
+    /// subtitle codec.
+    pub fn subtitle_header(&self) -> Option<&[u8]> {
+        unsafe {
+            let header = (*self.as_ptr()).subtitle_header;
+            let size = (*self.as_ptr()).subtitle_header_size;
+
+            if header.is_null() || size <= 0 {
+                None
+            } else {
+                Some(slice::from_raw_parts(header, size as usize))
+            }
+        }
+    }
+
+    /// Replaces the ASS header with a copy of `value`, or removes it if
+    /// `value` is empty.
+    pub fn set_subtitle_header(&mut self, value: &[u8]) -> Result<(), Error> {
+        let size = c_int::try_from(value.len()).map_err(|_| Error::Other { errno: EINVAL })?;
+
+        unsafe {
+            let ptr = self.as_mut_ptr();
+            av_freep(&mut (*ptr).subtitle_header as *mut *mut u8 as *mut c_void);
+            (*ptr).subtitle_header_size = 0;
+
+            if value.is_empty() {
+                return Ok(());
+            }
+
+            // Keep a NUL terminator, FFmpeg treats the header as a string.
+            let header = av_mallocz(value.len() + 1) as *mut u8;
+
+            if header.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            ptr::copy_nonoverlapping(value.as_ptr(), header, value.len());
+            (*ptr).subtitle_header = header;
+            (*ptr).subtitle_header_size = size;
+        }
+
+        Ok(())
+    }
+
+    /// Global codec data, see [`Parameters::extradata`][super::Parameters::extradata].
+    pub fn extradata(&self) -> Option<&[u8]> {
+        unsafe { extradata::get((*self.as_ptr()).extradata, (*self.as_ptr()).extradata_size) }
+    }
+
+    /// Replaces the extradata with a padded copy of `value`, or removes it if
+    /// `value` is empty.
+    ///
+    /// Decoders read the extradata when they are opened, e.g. the SPS and PPS
+    /// of H.264 streams whose parameter sets are sent out of band.
+    pub fn set_extradata(&mut self, value: &[u8]) -> Result<(), Error> {
+        unsafe {
+            let ptr = self.as_mut_ptr();
+            extradata::set(&mut (*ptr).extradata, &mut (*ptr).extradata_size, value)
+        }
+    }
+
     pub fn set_parameters<P: AsPtr<AVCodecParameters>>(
         &mut self,
         parameters: P,
@@ -174,6 +238,8 @@ impl AsMutPtr<AVCodecContext> for Context {
 }
 
 impl option::Settable<AVCodecContext> for Context {}
+impl option::Gettable<AVCodecContext> for Context {}
+impl option::Iterable<AVCodecContext> for Context {}
 
 #[cfg(test)]
 mod tests {
@@ -193,4 +259,113 @@ mod tests {
 
         // Drop occurs at end of scope; success is lack of crash.
     }
+
+    #[test]
+    fn get_options() {
+        use crate::option::{Gettable, Settable};
+
+        let mut ctx = Context::new();
+        ctx.set_int("g", 48).unwrap();
+        ctx.set_rational("aspect", (4, 3)).unwrap();
+
+        assert_eq!(ctx.get_int("g").unwrap(), 48);
+        assert_eq!(ctx.get_str("g").unwrap(), "48");
+        assert_eq!(ctx.get_rational("aspect").unwrap(), crate::Rational(4, 3));
+        assert!(ctx.get_int("no_such_option").is_err());
+    }
+
+    #[test]
+    fn extradata_round_trip() {
+        let mut ctx = Context::new();
+        assert_eq!(ctx.extradata(), None);
+
+        ctx.set_extradata(&[1, 2, 3]).unwrap();
+        assert_eq!(ctx.extradata(), Some(&[1, 2, 3][..]));
+
+        let decoder = ctx.decoder();
+        let mut parameters = crate::codec::Parameters::from(&decoder);
+        assert_eq!(parameters.extradata(), Some(&[1, 2, 3][..]));
+
+        parameters.set_extradata(&[]).unwrap();
+        assert_eq!(parameters.extradata(), None);
+    }
+
+    #[test]
+    fn subtitle_header() {
+        let mut ctx = Context::new();
+
+        ctx.set_subtitle_header(b"[Script Info]").unwrap();
+        assert_eq!(ctx.subtitle_header(), Some(&b"[Script Info]"[..]));
+
+        ctx.set_subtitle_header(&[]).unwrap();
+        assert_eq!(ctx.subtitle_header(), None);
+    }
+
+    #[test]
+    fn child_constants() {
+        use crate::option::Iterable;
+
+        let codec = crate::codec::encoder::find(Id::AAC).unwrap();
+        let ctx = Context::new_with_codec(codec);
+
+        let profile = ctx.options().find(|o| o.name() == "profile").unwrap();
+        let constants = profile.constants();
+
+        assert!(constants.iter().any(|c| c.name() == "aac_low"));
+        assert!(constants.iter().all(|c| c.unit() == profile.unit()));
+    }
+
+    #[test]
+    fn serialize_children() {
+        use crate::option::{Gettable, SerializeFlags, Settable};
+
+        let codec = crate::codec::encoder::find(Id::AAC).unwrap();
+        let mut ctx = Context::new_with_codec(codec);
+        ctx.set_str("aac_pce", "1").unwrap();
+
+        let flags = SerializeFlags::SKIP_DEFAULTS;
+        assert!(!ctx.serialize(flags).unwrap().contains("aac_pce="));
+
+        let flags = flags | SerializeFlags::SEARCH_CHILDREN;
+        assert!(ctx.serialize(flags).unwrap().contains("aac_pce="));
+    }
+
+    #[test]
+    fn iterate_options() {
+        use crate::option::{Iterable, Value};
+
+        let ctx = Context::new();
+
+        let bitrate = ctx.options().find(|o| o.name() == "b").unwrap();
+        assert_eq!(bitrate.default(), Value::Int(200_000));
+
+        let flags = ctx.options().find(|o| o.name() == "flags").unwrap();
+        assert!(flags
+            .constants()
+            .iter()
+            .any(|c| c.name() == "global_header"));
+    }
+
+    #[test]
+    fn options_from_string() {
+        use crate::option::{Gettable, SerializeFlags, Settable};
+        use crate::Dictionary;
+
+        let mut ctx = Context::new();
+        ctx.set_from_string("g=12:bf=2", "=", ":").unwrap();
+
+        assert_eq!(ctx.get_int("g").unwrap(), 12);
+        assert_eq!(ctx.get_int("bf").unwrap(), 2);
+
+        let serialized = ctx.serialize(SerializeFlags::SKIP_DEFAULTS).unwrap();
+        assert!(serialized.contains("g=12"));
+
+        let mut dict = Dictionary::new();
+        dict.set("qmin", "5");
+        dict.set("not_an_option", "1");
+
+        let remaining = ctx.set_dict(dict).unwrap();
+        assert_eq!(ctx.get_int("qmin").unwrap(), 5);
+        assert_eq!(remaining.get("not_an_option"), Some("1"));
+    }
 }
@@ -114,6 +114,19 @@ impl Decoder {
     pub fn time_base(&self) -> Rational {
         unsafe { Rational::from((*self.as_ptr()).time_base) }
     }
+
+    /// Time base of the packets sent to the decoder, usually the stream time base.
+    ///
+    /// Subtitle decoders need it to compute [`Subtitle::pts`][crate::Subtitle::pts].
+    pub fn set_packet_time_base<R: Into<Rational>>(&mut self, value: R) {
+        unsafe {
+            (*self.as_mut_ptr()).pkt_timebase = value.into().into();
+        }
+    }
+
+    pub fn packet_time_base(&self) -> Rational {
+        unsafe { Rational::from((*self.as_ptr()).pkt_timebase) }
+    }
 }
 
 impl Deref for Decoder {
@@ -10,6 +10,9 @@ use crate::{packet, Error};
 pub struct Subtitle(pub Opened);
 
 impl Subtitle {
+    /// Decodes `packet` into `out`, replacing its previous content.
+    ///
+    /// The previous rects of `out` are freed.
     pub fn decode<P: packet::Ref>(
         &mut self,
         packet: &P,
@@ -18,6 +21,9 @@ impl Subtitle {
         unsafe {
             let mut got: c_int = 0;
 
+            // The decoder overwrites `out` without releasing its rects.
+            avsubtitle_free(out.as_mut_ptr());
+
             match avcodec_decode_subtitle2(
                 self.as_mut_ptr(),
                 out.as_mut_ptr(),
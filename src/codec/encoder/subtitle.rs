@@ -2,11 +2,15 @@ use std::ops::{Deref, DerefMut};
 use std::ptr;
 
 use crate::ffi::*;
-use libc::c_int;
+use libc::{c_int, EINVAL};
 
 use super::Encoder as Super;
 use crate::codec::{traits, Context};
-use crate::{AsMutPtr, Error};
+use crate::rescale::TIME_BASE;
+use crate::{AsMutPtr, Error, Packet, Rational, Rescale};
+
+// Same upper bound as the ffmpeg command line tool.
+const MAX_PACKET_SIZE: usize = 1024 * 1024;
 
 pub struct Subtitle(pub Super);
 
@@ -104,6 +108,77 @@ impl Encoder {
     }
 }
 
+impl Encoder {
+    /// Encodes `subtitle` into a newly allocated packet.
+    ///
+    /// The display start time of `subtitle` is folded into the packet pts, and
+    /// both pts and duration are expressed in the encoder time base.
+    pub fn encode_to_packet(&mut self, subtitle: &crate::Subtitle) -> Result<Packet, Error> {
+        let pts = subtitle.pts().ok_or(Error::Other { errno: EINVAL })?;
+
+        unsafe {
+            // Encoders reject a non-zero start_display_time. The copy shares the
+            // rects of `subtitle` and is never freed.
+            let mut sub: AVSubtitle = ptr::read(subtitle.as_ptr());
+            sub.pts = pts + i64::from(sub.start_display_time) * 1000;
+            if sub.end_display_time != u32::MAX {
+                sub.end_display_time = sub.end_display_time.saturating_sub(sub.start_display_time);
+            }
+            sub.start_display_time = 0;
+
+            let mut size = estimate_size(subtitle);
+
+            loop {
+                let mut packet = Packet::new(size);
+                let data = packet
+                    .data_mut()
+                    .map_or(ptr::null_mut(), |d| d.as_mut_ptr());
+
+                match avcodec_encode_subtitle(self.0.as_mut_ptr(), data, size as c_int, &sub) {
+                    e if e < 0 => match Error::from(e) {
+                        Error::BufferTooSmall if size < MAX_PACKET_SIZE => {
+                            size = (size * 4).min(MAX_PACKET_SIZE);
+                        }
+                        error => return Err(error),
+                    },
+
+                    written => {
+                        let time_base = Rational::from((*self.as_ptr()).time_base);
+
+                        packet.shrink(written as usize);
+                        packet.set_pts(Some(sub.pts.rescale(TIME_BASE, time_base)));
+                        packet.set_dts(packet.pts());
+
+                        if sub.end_display_time != u32::MAX {
+                            packet.set_duration(
+                                i64::from(sub.end_display_time).rescale((1, 1000), time_base),
+                            );
+                        }
+
+                        return Ok(packet);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn estimate_size(subtitle: &crate::Subtitle) -> usize {
+    let content: usize = subtitle
+        .rects()
+        .map(|rect| {
+            let text = rect.text().map_or(0, str::len) + rect.ass().map_or(0, str::len);
+            let bitmap = rect.bitmap().map_or(0, |b| {
+                (b.width() * b.height()) as usize * 2 + b.palette().len() * 4
+            });
+
+            text + bitmap
+        })
+        .sum();
+
+    (1024 + content * 2).min(MAX_PACKET_SIZE)
+}
+
 impl Deref for Encoder {
     type Target = Subtitle;
 
@@ -0,0 +1,345 @@
+//! Builders for the decoder configurations stored as extradata by the MP4 and
+//! Matroska muxers.
+
+use std::ptr;
+use std::slice;
+
+use super::nal::{self, BitReader};
+use crate::ffi::*;
+use crate::Error;
+use libc::{c_int, c_void, EINVAL};
+
+pub use super::nal::split_annex_b;
+
+// H.264 profiles whose SPS has chroma_format_idc and bit depth fields.
+const H264_CHROMA_PROFILES: &[u8] = &[100, 110, 122, 244, 44, 83, 86, 118, 128, 138, 139, 134, 135];
+
+/// Builds an `AVCDecoderConfigurationRecord` (`avcC`) from H.264 SPS and
+/// PPS NAL units without start codes, e.g. as returned by
+/// [`split_annex_b`].
+pub fn avcc(sps: &[&[u8]], pps: &[&[u8]]) -> Result<Vec<u8>, Error> {
+    let first = sps.first().ok_or(Error::Other { errno: EINVAL })?;
+
+    if sps.len() > 31
+        || pps.len() > 255
+        || !sps.iter().all(|nal| nal.len() >= 4 && nal[0] & 0x1f == 7)
+        || !pps.iter().all(|nal| !nal.is_empty() && nal[0] & 0x1f == 8)
+    {
+        return Err(Error::Other { errno: EINVAL });
+    }
+
+    let profile = first[1];
+
+    let mut record = vec![
+        1,
+        profile,
+        first[2],
+        first[3],
+        0xfc | 3,
+        0xe0 | sps.len() as u8,
+    ];
+    write_units(&mut record, sps)?;
+    record.push(pps.len() as u8);
+    write_units(&mut record, pps)?;
+
+    if !matches!(profile, 66 | 77 | 88) {
+        let (chroma_format, luma_depth, chroma_depth) = if H264_CHROMA_PROFILES.contains(&profile) {
+            avc_format(&nal::unescape(first)).ok_or(Error::InvalidData)?
+        } else {
+            (1, 0, 0)
+        };
+
+        record.extend_from_slice(&[
+            0xfc | chroma_format as u8,
+            0xf8 | luma_depth as u8,
+            0xf8 | chroma_depth as u8,
+            0,
+        ]);
+    }
+
+    Ok(record)
+}
+
+/// Builds an `HEVCDecoderConfigurationRecord` (`hvcC`) from HEVC VPS, SPS and
+/// PPS NAL units without start codes.
+pub fn hvcc(vps: &[&[u8]], sps: &[&[u8]], pps: &[&[u8]]) -> Result<Vec<u8>, Error> {
+    let first = sps.first().ok_or(Error::Other { errno: EINVAL })?;
+
+    for (units, kind) in [(vps, 32), (sps, 33), (pps, 34)] {
+        if !units
+            .iter()
+            .all(|nal| nal.len() >= 2 && (nal[0] >> 1) & 0x3f == kind)
+        {
+            return Err(Error::Other { errno: EINVAL });
+        }
+    }
+
+    let data = nal::unescape(first);
+
+    if data.len() < 15 {
+        return Err(Error::InvalidData);
+    }
+
+    let (sub_layers, nested, chroma_format, luma_depth, chroma_depth) =
+        hevc_format(&data).ok_or(Error::InvalidData)?;
+
+    let mut record = vec![1];
+    record.extend_from_slice(&data[3..15]);
+    record.extend_from_slice(&[
+        0xf0,
+        0x00,
+        0xfc,
+        0xfc | chroma_format as u8,
+        0xf8 | luma_depth as u8,
+        0xf8 | chroma_depth as u8,
+        0,
+        0,
+        ((sub_layers << 3) | (nested << 2) | 3) as u8,
+    ]);
+
+    let arrays = [(32, vps), (33, sps), (34, pps)];
+    record.push(arrays.iter().filter(|(_, units)| !units.is_empty()).count() as u8);
+
+    for (kind, units) in arrays {
+        if units.is_empty() {
+            continue;
+        }
+
+        let count = u16::try_from(units.len()).map_err(|_| Error::Other { errno: EINVAL })?;
+
+        record.push(0x80 | kind);
+        record.extend_from_slice(&count.to_be_bytes());
+        write_units(&mut record, units)?;
+    }
+
+    Ok(record)
+}
+
+// chroma_format_idc and the bit depths of an unescaped H.264 SPS.
+fn avc_format(sps: &[u8]) -> Option<(u32, u32, u32)> {
+    let mut reader = BitReader::new(sps.get(4..)?);
+
+    reader.ue()?;
+    let chroma_format = reader.ue()?;
+
+    if chroma_format == 3 {
+        reader.skip(1)?;
+    }
+
+    Some((chroma_format, reader.ue()?, reader.ue()?))
+}
+
+// The number of temporal layers, temporal_id_nesting_flag, chroma_format_idc
+// and the bit depths of an unescaped HEVC SPS.
+fn hevc_format(sps: &[u8]) -> Option<(u32, u32, u32, u32, u32)> {
+    let mut reader = BitReader::new(sps.get(2..)?);
+
+    reader.skip(4)?;
+    let max_sub_layers = reader.bits(3)?;
+    let nested = reader.bit()?;
+
+    // The general profile, tier and level.
+    reader.skip(96)?;
+
+    let mut present = Vec::new();
+
+    for _ in 0..max_sub_layers {
+        present.push((reader.bit()?, reader.bit()?));
+    }
+
+    if max_sub_layers > 0 {
+        reader.skip(2 * (8 - max_sub_layers as usize))?;
+    }
+
+    for (profile, level) in present {
+        reader.skip(88 * profile as usize + 8 * level as usize)?;
+    }
+
+    reader.ue()?;
+    let chroma_format = reader.ue()?;
+
+    if chroma_format == 3 {
+        reader.skip(1)?;
+    }
+
+    // The picture size and the conformance window.
+    reader.ue()?;
+    reader.ue()?;
+
+    if reader.bit()? == 1 {
+        for _ in 0..4 {
+            reader.ue()?;
+        }
+    }
+
+    Some((
+        max_sub_layers + 1,
+        nested,
+        chroma_format,
+        reader.ue()?,
+        reader.ue()?,
+    ))
+}
+
+// The extradata in `data`, or `None` if there is none.
+pub(crate) unsafe fn get<'a>(data: *const u8, size: c_int) -> Option<&'a [u8]> {
+    if data.is_null() || size <= 0 {
+        None
+    } else {
+        Some(slice::from_raw_parts(data, size as usize))
+    }
+}
+
+// Replaces the extradata in `data` with a padded copy of `value`, allocated
+// with av_malloc() as FFmpeg frees it with av_free().
+pub(crate) unsafe fn set(data: &mut *mut u8, size: &mut c_int, value: &[u8]) -> Result<(), Error> {
+    let len = c_int::try_from(value.len()).map_err(|_| Error::Other { errno: EINVAL })?;
+
+    av_freep(data as *mut *mut u8 as *mut c_void);
+    *size = 0;
+
+    if value.is_empty() {
+        return Ok(());
+    }
+
+    let copy = av_mallocz(value.len() + AV_INPUT_BUFFER_PADDING_SIZE as usize) as *mut u8;
+
+    if copy.is_null() {
+        return Err(Error::Other {
+            errno: libc::ENOMEM,
+        });
+    }
+
+    ptr::copy_nonoverlapping(value.as_ptr(), copy, value.len());
+    *data = copy;
+    *size = len;
+
+    Ok(())
+}
+
+// Appends every unit with a 16-bit length prefix.
+fn write_units(record: &mut Vec<u8>, units: &[&[u8]]) -> Result<(), Error> {
+    for unit in units {
+        let len = u16::try_from(unit.len()).map_err(|_| Error::Other { errno: EINVAL })?;
+
+        record.extend_from_slice(&len.to_be_bytes());
+        record.extend_from_slice(unit);
+    }
+
+    Ok(())
+}
+
+/// The identification header of an Opus stream, which FFmpeg expects as the
+/// extradata of Opus decoders, see RFC 7845.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct OpusHead {
+    pub channels: u8,
+    /// Number of samples at 48 kHz to discard at the start of the stream.
+    pub pre_skip: u16,
+    /// Sample rate of the original input, for information only.
+    pub input_rate: u32,
+    /// Gain to apply to the decoded output, in 1/256 dB.
+    pub output_gain: i16,
+    /// Required for more than two channels.
+    pub mapping: Option<ChannelMapping>,
+}
+
+/// Assignment of the channels of an Opus stream to its coded streams.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct ChannelMapping {
+    /// 1 for the Vorbis channel order, 255 for an undefined order.
+    pub family: u8,
+    pub streams: u8,
+    /// Number of streams that code two channels.
+    pub coupled_streams: u8,
+    /// Index of the decoded channel for every output channel, or 255 for a
+    /// silent channel.
+    pub channels: Vec<u8>,
+}
+
+impl OpusHead {
+    /// A header with the pre-skip of libopus and no output gain.
+    pub fn new(channels: u8, input_rate: u32) -> Self {
+        OpusHead {
+            channels,
+            pre_skip: 312,
+            input_rate,
+            output_gain: 0,
+            mapping: None,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut head = b"OpusHead".to_vec();
+
+        head.push(1);
+        head.push(self.channels);
+        head.extend_from_slice(&self.pre_skip.to_le_bytes());
+        head.extend_from_slice(&self.input_rate.to_le_bytes());
+        head.extend_from_slice(&self.output_gain.to_le_bytes());
+
+        match &self.mapping {
+            None if (1..=2).contains(&self.channels) => head.push(0),
+
+            Some(mapping)
+                if mapping.family != 0
+                    && mapping.streams > 0
+                    && mapping.coupled_streams <= mapping.streams
+                    && mapping.channels.len() == usize::from(self.channels)
+                    && mapping.channels.iter().all(|&channel| {
+                        channel == 255
+                            || u32::from(channel)
+                                < u32::from(mapping.streams) + u32::from(mapping.coupled_streams)
+                    }) =>
+            {
+                head.push(mapping.family);
+                head.push(mapping.streams);
+                head.push(mapping.coupled_streams);
+                head.extend_from_slice(&mapping.channels);
+            }
+
+            _ => return Err(Error::Other { errno: EINVAL }),
+        }
+
+        Ok(head)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records() {
+        let sps = [0x67, 0x64, 0x00, 0x28, 0xac, 0xd9, 0x40];
+        let pps = [0x68, 0xeb, 0xe3, 0xcb];
+
+        let avcc = avcc(&[&sps], &[&pps]).unwrap();
+        assert_eq!(avcc[..6], [1, 0x64, 0x00, 0x28, 0xff, 0xe1]);
+        assert_eq!(avcc[avcc.len() - 4..], [0xfd, 0xf8, 0xf8, 0]);
+        assert_eq!(avcc.len(), 6 + 2 + sps.len() + 1 + 2 + pps.len() + 4);
+
+        let vps = [0x40, 0x01, 0x0c];
+        let sps = [
+            0x42, 0x01, 0x01, 0x01, 0x60, 0, 0, 3, 0, 0x90, 0, 0, 3, 0, 0, 3, 0, 0x5d, 0xa1, 0x22,
+            0x5c,
+        ];
+        let pps = [0x44, 0x01, 0xc1];
+
+        let hvcc = hvcc(&[&vps], &[&sps], &[&pps]).unwrap();
+        assert_eq!(
+            hvcc[..13],
+            [1, 0x01, 0x60, 0, 0, 0, 0x90, 0, 0, 0, 0, 0, 0x5d]
+        );
+        assert_eq!(
+            hvcc[13..23],
+            [0xf0, 0, 0xfc, 0xfd, 0xf8, 0xf8, 0, 0, 0x0f, 3]
+        );
+        assert_eq!(hvcc[23..26], [0xa0, 0, 1]);
+
+        let head = OpusHead::new(2, 44100).to_bytes().unwrap();
+        assert_eq!(head.len(), 19);
+        assert_eq!(head[9..12], [2, 0x38, 0x01]);
+        assert!(OpusHead::new(6, 48000).to_bytes().is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use std::marker::PhantomData;
+
+use crate::ffi::*;
+use crate::util::format;
+use crate::util::hwcontext::DeviceType;
+use libc::c_int;
+
+bitflags::bitflags! {
+    /// Ways a hardware configuration can be set up on a codec context.
+    ///
+    /// The values come from an anonymous enum in libavcodec/codec.h.
+    #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+    pub struct Methods: c_int {
+        /// Through `hw_device_ctx`.
+        const HW_DEVICE_CTX = 0x01;
+        /// Through `hw_frames_ctx`.
+        const HW_FRAMES_CTX = 0x02;
+        /// Without setup, e.g. by the codec itself.
+        const INTERNAL      = 0x04;
+        /// Through the legacy hwaccel specific fields.
+        const AD_HOC        = 0x08;
+    }
+}
+
+/// A hardware configuration supported by a codec.
+#[derive(Copy, Clone)]
+pub struct HwConfig<'a> {
+    config: &'a AVCodecHWConfig,
+}
+
+impl<'a> HwConfig<'a> {
+    pub fn as_ptr(&self) -> *const AVCodecHWConfig {
+        self.config
+    }
+
+    /// Pixel format of the frames in hardware memory.
+    pub fn format(&self) -> format::Pixel {
+        format::Pixel::from(self.config.pix_fmt)
+    }
+
+    pub fn methods(&self) -> Methods {
+        Methods::from_bits_truncate(self.config.methods)
+    }
+
+    pub fn device_type(&self) -> DeviceType {
+        DeviceType::from(self.config.device_type)
+    }
+}
+
+impl std::fmt::Debug for HwConfig<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("HwConfig")
+            .field("format", &self.format())
+            .field("methods", &self.methods())
+            .field("device_type", &self.device_type())
+            .finish()
+    }
+}
+
+/// Iterator over the hardware configurations of a codec.
+pub struct HwConfigIter<'a> {
+    codec: *const AVCodec,
+    index: c_int,
+    _marker: PhantomData<&'a AVCodec>,
+}
+
+impl<'a> HwConfigIter<'a> {
+    pub(crate) unsafe fn new(codec: *const AVCodec) -> Self {
+        HwConfigIter {
+            codec,
+            index: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a> Iterator for HwConfigIter<'a> {
+    type Item = HwConfig<'a>;
+
+    fn next(&mut self) -> Option<HwConfig<'a>> {
+        unsafe {
+            let config = avcodec_get_hw_config(self.codec, self.index).as_ref()?;
+            self.index += 1;
+
+            Some(HwConfig { config })
+        }
+    }
+}
+
+impl std::iter::FusedIterator for HwConfigIter<'_> {}
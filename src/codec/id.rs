@@ -653,6 +653,12 @@ impl Id {
     pub fn name(&self) -> &'static str {
         unsafe { utils::str_from_c_ptr(avcodec_get_name((*self).into())) }
     }
+
+    /// Human-readable name of `profile` for this codec, e.g. `High` for the
+    /// H.264 profile 100.
+    pub fn profile_name(&self, profile: i32) -> Option<&'static str> {
+        unsafe { utils::optional_str_from_c_ptr(avcodec_profile_name((*self).into(), profile)) }
+    }
 }
 
 impl From<AVCodecID> for Id {
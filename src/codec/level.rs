@@ -0,0 +1,167 @@
+//! Codec levels, in the representation used by FFmpeg, i.e. `level_idc` for
+//! H.264 (`31` for 3.1) and HEVC (`93` for 3.1), and `seq_level_idx` for AV1
+//! (`5` for 3.1).
+
+use super::Id;
+use crate::Rational;
+
+// Level, MaxMBPS, MaxFS and MaxBR in kbit/s of the Baseline, Main and
+// Extended profiles, from Table A-1 of H.264. Level 1b is left out.
+const H264: &[(i32, u64, u64, u64)] = &[
+    (10, 1_485, 99, 64),
+    (11, 3_000, 396, 192),
+    (12, 6_000, 396, 384),
+    (13, 11_880, 396, 768),
+    (20, 11_880, 396, 2_000),
+    (21, 19_800, 792, 4_000),
+    (22, 20_250, 1_620, 4_000),
+    (30, 40_500, 1_620, 10_000),
+    (31, 108_000, 3_600, 14_000),
+    (32, 216_000, 5_120, 20_000),
+    (40, 245_760, 8_192, 20_000),
+    (41, 245_760, 8_192, 50_000),
+    (42, 522_240, 8_704, 50_000),
+    (50, 589_824, 22_080, 135_000),
+    (51, 983_040, 36_864, 240_000),
+    (52, 2_073_600, 36_864, 240_000),
+    (60, 4_177_920, 139_264, 240_000),
+    (61, 8_355_840, 139_264, 480_000),
+    (62, 16_711_680, 139_264, 800_000),
+];
+
+// Level, MaxLumaPs, MaxLumaSr and MaxBR in kbit/s of the Main tier, from
+// Tables A.8 and A.9 of HEVC.
+const HEVC: &[(i32, u64, u64, u64)] = &[
+    (30, 36_864, 552_960, 128),
+    (60, 122_880, 3_686_400, 1_500),
+    (63, 245_760, 7_372_800, 3_000),
+    (90, 552_960, 16_588_800, 6_000),
+    (93, 983_040, 33_177_600, 10_000),
+    (120, 2_228_224, 66_846_720, 12_000),
+    (123, 2_228_224, 133_693_440, 20_000),
+    (150, 8_912_896, 267_386_880, 25_000),
+    (153, 8_912_896, 534_773_760, 40_000),
+    (156, 8_912_896, 1_069_547_520, 60_000),
+    (180, 35_651_584, 1_069_547_520, 60_000),
+    (183, 35_651_584, 2_139_095_040, 120_000),
+    (186, 35_651_584, 4_278_190_080, 240_000),
+];
+
+// seq_level_idx, MaxPicSize, MaxHSize, MaxVSize, MaxDisplayRate and
+// MaxBitrate in kbit/s of the Main tier, from Annex A.3 of AV1.
+const AV1: &[(i32, u64, u64, u64, u64, u64)] = &[
+    (0, 147_456, 2_048, 1_152, 4_423_680, 1_500),
+    (1, 278_784, 2_816, 1_584, 8_363_520, 3_000),
+    (4, 665_856, 4_352, 2_448, 19_975_680, 6_000),
+    (5, 1_065_024, 5_504, 3_096, 31_950_720, 10_000),
+    (8, 2_359_296, 6_144, 3_456, 70_778_880, 12_000),
+    (9, 2_359_296, 6_144, 3_456, 141_557_760, 20_000),
+    (12, 8_912_896, 8_192, 4_352, 267_386_880, 30_000),
+    (13, 8_912_896, 8_192, 4_352, 534_773_760, 40_000),
+    (14, 8_912_896, 8_192, 4_352, 1_069_547_520, 60_000),
+    (16, 35_651_584, 16_384, 8_704, 1_069_547_520, 60_000),
+    (17, 35_651_584, 16_384, 8_704, 2_139_095_040, 100_000),
+    (18, 35_651_584, 16_384, 8_704, 4_278_190_080, 160_000),
+];
+
+/// Human-readable name of `level` for the codec `id`, e.g. `3.1`.
+///
+/// Returns `None` for unknown levels and for codecs other than H.264, HEVC
+/// and AV1.
+pub fn name(id: Id, level: i32) -> Option<String> {
+    match id {
+        Id::H264 if level == 9 => Some(String::from("1b")),
+        Id::H264 if level > 0 => Some(format!("{}.{}", level / 10, level % 10)),
+        Id::HEVC if level > 0 && level % 3 == 0 => {
+            Some(format!("{}.{}", level / 30, level % 30 / 3))
+        }
+        Id::AV1 if (0..24).contains(&level) => Some(format!("{}.{}", 2 + (level >> 2), level & 3)),
+
+        _ => None,
+    }
+}
+
+/// The lowest level of the codec `id` that allows encoding a `width` x
+/// `height` stream at `frame_rate` and `bit_rate` bit/s.
+///
+/// The limits of the Main profile and tier are used, a `bit_rate` of zero is
+/// not checked. Returns `None` if no level is high enough or the codec is not
+/// H.264, HEVC or AV1.
+pub fn select(id: Id, width: u32, height: u32, frame_rate: Rational, bit_rate: u64) -> Option<i32> {
+    if frame_rate.numerator() <= 0 || frame_rate.denominator() <= 0 {
+        return None;
+    }
+
+    let (width, height) = (u64::from(width), u64::from(height));
+    let (num, den) = (
+        frame_rate.numerator() as u64,
+        frame_rate.denominator() as u64,
+    );
+    let kbits = bit_rate.div_ceil(1000);
+
+    match id {
+        Id::H264 => {
+            let (mbs_w, mbs_h) = (width.div_ceil(16), height.div_ceil(16));
+            let frame_size = mbs_w * mbs_h;
+
+            H264.iter()
+                .find(|&&(_, max_rate, max_size, max_bits)| {
+                    frame_size <= max_size
+                        && mbs_w * mbs_w <= max_size * 8
+                        && mbs_h * mbs_h <= max_size * 8
+                        && frame_size * num <= max_rate * den
+                        && kbits <= max_bits
+                })
+                .map(|&(level, ..)| level)
+        }
+
+        Id::HEVC => {
+            let picture_size = width * height;
+
+            HEVC.iter()
+                .find(|&&(_, max_size, max_rate, max_bits)| {
+                    picture_size <= max_size
+                        && width * width <= max_size * 8
+                        && height * height <= max_size * 8
+                        && picture_size * num <= max_rate * den
+                        && kbits <= max_bits
+                })
+                .map(|&(level, ..)| level)
+        }
+
+        Id::AV1 => {
+            let picture_size = width * height;
+
+            AV1.iter()
+                .find(|&&(_, max_size, max_w, max_h, max_rate, max_bits)| {
+                    picture_size <= max_size
+                        && width <= max_w
+                        && height <= max_h
+                        && picture_size * num <= max_rate * den
+                        && kbits <= max_bits
+                })
+                .map(|&(level, ..)| level)
+        }
+
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels() {
+        assert_eq!(name(Id::H264, 31).as_deref(), Some("3.1"));
+        assert_eq!(name(Id::HEVC, 93).as_deref(), Some("3.1"));
+        assert_eq!(name(Id::AV1, 8).as_deref(), Some("4.0"));
+
+        let fps = Rational::new(30, 1);
+        assert_eq!(select(Id::H264, 1280, 720, fps, 5_000_000), Some(31));
+        assert_eq!(select(Id::H264, 1920, 1080, fps, 8_000_000), Some(40));
+        assert_eq!(select(Id::HEVC, 1920, 1080, fps, 8_000_000), Some(120));
+        assert_eq!(select(Id::AV1, 1920, 1080, fps, 8_000_000), Some(8));
+        assert_eq!(select(Id::H264, 16384, 16384, fps, 0), None);
+    }
+}
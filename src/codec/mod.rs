@@ -22,7 +22,10 @@ pub mod capabilities;
 pub use self::capabilities::Capabilities;
 
 pub mod codec;
-pub use self::codec::{Audio, Codec, Video};
+pub use self::codec::{list, Audio, Codec, Video};
+
+pub mod hw_config;
+pub use self::hw_config::HwConfig;
 
 pub mod parameters;
 pub use self::parameters::{Parameters, ParametersMut, ParametersRef};
@@ -46,6 +49,12 @@ pub use profile_old as profile;
 pub mod profile;
 pub use self::profile::Profile;
 
+pub mod level;
+mod rfc6381;
+
+pub mod extradata;
+mod nal;
+
 pub mod props;
 pub use self::props::CodecProperties;
 
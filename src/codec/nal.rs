@@ -0,0 +1,130 @@
+//! Parsing of H.264 and HEVC NAL units.
+
+/// Splits Annex B `data` into NAL units, without their start codes.
+///
+/// Returns `None` if `data` does not start with a start code.
+pub fn split_annex_b(data: &[u8]) -> Option<Vec<&[u8]>> {
+    if !data.starts_with(&[0, 0, 1]) && !data.starts_with(&[0, 0, 0, 1]) {
+        return None;
+    }
+
+    let mut units = Vec::new();
+    let mut start = None;
+    let mut i = 0;
+
+    while i + 3 <= data.len() {
+        if data[i..i + 3] == [0, 0, 1] {
+            if let Some(start) = start {
+                units.push(trim(&data[start..i]));
+            }
+
+            i += 3;
+            start = Some(i);
+        } else {
+            i += 1;
+        }
+    }
+
+    if let Some(start) = start {
+        units.push(&data[start..]);
+    }
+
+    units.retain(|nal| !nal.is_empty());
+
+    Some(units)
+}
+
+// Removes the zero bytes before the next start code.
+fn trim(nal: &[u8]) -> &[u8] {
+    let len = nal.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    &nal[..len]
+}
+
+/// Removes the emulation prevention bytes of a NAL unit.
+pub fn unescape(nal: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(nal.len());
+    let mut zeros = 0;
+
+    for &byte in nal {
+        if zeros >= 2 && byte == 3 {
+            zeros = 0;
+            continue;
+        }
+
+        zeros = if byte == 0 { zeros + 1 } else { 0 };
+        data.push(byte);
+    }
+
+    data
+}
+
+/// Reads the bits of an unescaped NAL unit, most significant first.
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        BitReader { data, position: 0 }
+    }
+
+    pub fn bit(&mut self) -> Option<u32> {
+        let byte = self.data.get(self.position / 8)?;
+        let bit = (byte >> (7 - self.position % 8)) & 1;
+        self.position += 1;
+
+        Some(u32::from(bit))
+    }
+
+    pub fn bits(&mut self, count: u32) -> Option<u32> {
+        (0..count).try_fold(0, |value, _| Some((value << 1) | self.bit()?))
+    }
+
+    pub fn skip(&mut self, count: usize) -> Option<()> {
+        self.position += count;
+
+        if self.position <= self.data.len() * 8 {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    /// An unsigned Exp-Golomb code.
+    pub fn ue(&mut self) -> Option<u32> {
+        let mut zeros = 0;
+
+        while self.bit()? == 0 {
+            zeros += 1;
+
+            if zeros > 31 {
+                return None;
+            }
+        }
+
+        Some((1 << zeros) - 1 + self.bits(zeros)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let data = [0, 0, 0, 1, 0x67, 0x42, 0, 0, 1, 0x68, 0, 0, 3, 1];
+        let units = split_annex_b(&data).unwrap();
+
+        assert_eq!(units, [&[0x67, 0x42][..], &[0x68, 0, 0, 3, 1][..]]);
+        assert_eq!(unescape(units[1]), [0x68, 0, 0, 1]);
+
+        // 1, 010, 011, 00100
+        let mut reader = BitReader::new(&[0b1010_0110, 0b0100_0000]);
+        assert_eq!(reader.ue(), Some(0));
+        assert_eq!(reader.ue(), Some(1));
+        assert_eq!(reader.ue(), Some(2));
+        assert_eq!(reader.ue(), Some(3));
+        assert_eq!(split_annex_b(&[0x67]), None);
+    }
+}
@@ -4,7 +4,7 @@ use std::slice;
 
 use super::{Borrow, Flags, Mut, Ref, SideData};
 use crate::ffi::*;
-use crate::{format, Error, Rational};
+use crate::{format, Error, Rational, Timestamp};
 use libc::c_int;
 
 pub struct Packet(AVPacket);
@@ -125,6 +125,13 @@ impl Packet {
         }
     }
 
+    /// The presentation timestamp, in `time_base`, which is usually the
+    /// time base of the stream.
+    #[inline]
+    pub fn pts_ts<T: Into<Rational>>(&self, time_base: T) -> Option<Timestamp> {
+        self.pts().map(|pts| Timestamp::new(pts, time_base))
+    }
+
     #[inline]
     pub fn set_pts(&mut self, value: Option<i64>) {
         self.0.pts = value.unwrap_or(AV_NOPTS_VALUE);
@@ -138,6 +145,12 @@ impl Packet {
         }
     }
 
+    /// The decompression timestamp, in `time_base`.
+    #[inline]
+    pub fn dts_ts<T: Into<Rational>>(&self, time_base: T) -> Option<Timestamp> {
+        self.dts().map(|dts| Timestamp::new(dts, time_base))
+    }
+
     #[inline]
     pub fn set_dts(&mut self, value: Option<i64>) {
         self.0.dts = value.unwrap_or(AV_NOPTS_VALUE);
@@ -205,33 +218,26 @@ impl Packet {
         }
     }
 
+    /// Write the packet directly to `format`, without interleaving.
+    ///
+    /// See [`Output::write`][format::context::Output::write].
     #[inline]
-    pub fn write(&self, format: &mut format::context::Output) -> Result<bool, Error> {
-        unsafe {
-            if self.is_empty() {
-                return Err(Error::InvalidData);
-            }
-
-            match av_write_frame(format.as_mut_ptr(), self.as_ptr() as *mut _) {
-                1 => Ok(true),
-                0 => Ok(false),
-                e => Err(Error::from(e)),
-            }
-        }
+    pub fn write(
+        &self,
+        format: &mut format::context::Output,
+    ) -> Result<bool, format::context::WriteError> {
+        format.write(self)
     }
 
+    /// Write the packet to `format`, making sure packets are properly interleaved.
+    ///
+    /// See [`Output::write_interleaved`][format::context::Output::write_interleaved].
     #[inline]
-    pub fn write_interleaved(&self, format: &mut format::context::Output) -> Result<(), Error> {
-        unsafe {
-            if self.is_empty() {
-                return Err(Error::InvalidData);
-            }
-
-            match av_interleaved_write_frame(format.as_mut_ptr(), self.as_ptr() as *mut _) {
-                0 => Ok(()),
-                e => Err(Error::from(e)),
-            }
-        }
+    pub fn write_interleaved(
+        &mut self,
+        format: &mut format::context::Output,
+    ) -> Result<(), format::context::WriteError> {
+        format.write_interleaved(self)
     }
 }
 
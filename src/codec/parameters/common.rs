@@ -1,16 +1,22 @@
+use std::mem;
+
 use crate::macros::impl_for_many;
 
 use super::{Parameters, ParametersMut, ParametersRef};
 use crate::chroma::Location;
-use crate::codec::Id;
+use crate::codec::packet::SideData;
+use crate::codec::{extradata, level, rfc6381, Id};
 use crate::color;
+use crate::ffi::*;
 use crate::media;
-use crate::{FieldOrder, Rational};
+use crate::util::format;
+use crate::{Error, FieldOrder, Rational};
 
 #[cfg(feature = "ffmpeg_8_1")]
 use crate::format::AlphaMode;
 
 use crate::ChannelLayout;
+use libc::c_int;
 
 impl_for_many! {
     impl for Parameters, ParametersRef<'p>, ParametersMut<'p> {
@@ -22,10 +28,49 @@ impl_for_many! {
             unsafe { (*self.as_ptr()).codec_id.into() }
         }
 
-        // TODO: codec_tag
-        // TODO: extradata
-        // TODO: coded_side_data
-        // TODO: format (needs From<c_int> for format::Pixel and format::Sample)
+        /// The FourCC of the codec in the container, e.g. `avc1` as
+        /// `u32::from_le_bytes(*b"avc1")`, or 0 if unknown.
+        pub fn codec_tag(&self) -> u32 {
+            unsafe { (*self.as_ptr()).codec_tag }
+        }
+
+        /// Global codec data, like the `avcC` box of H.264 streams in MP4
+        /// files or the OpusHead of Opus streams.
+        pub fn extradata(&self) -> Option<&[u8]> {
+            unsafe { extradata::get((*self.as_ptr()).extradata, (*self.as_ptr()).extradata_size) }
+        }
+
+        /// Side data that applies to the whole stream, like the mastering
+        /// display metadata or the Dolby Vision configuration.
+        #[cfg(feature = "ffmpeg_6_1")]
+        pub fn coded_side_data(&self) -> impl ExactSizeIterator<Item = SideData<'_>> + '_ {
+            unsafe {
+                let par = self.as_ptr();
+                let count = (*par).nb_coded_side_data.max(0) as usize;
+
+                (0..count).map(move |i| SideData::wrap((*par).coded_side_data.add(i)))
+            }
+        }
+
+        /// The pixel format, for video streams.
+        pub fn pixel_format(&self) -> format::Pixel {
+            unsafe {
+                match (*self.as_ptr()).format {
+                    -1 => format::Pixel::None,
+                    value => format::Pixel::from(mem::transmute::<c_int, AVPixelFormat>(value)),
+                }
+            }
+        }
+
+        /// The sample format, for audio streams.
+        pub fn sample_format(&self) -> format::Sample {
+            unsafe {
+                match (*self.as_ptr()).format {
+                    -1 => format::Sample::None,
+                    value => format::Sample::from(mem::transmute::<c_int, AVSampleFormat>(value)),
+                }
+            }
+        }
 
         pub fn bit_rate(&self) -> i64 {
             unsafe { (*self.as_ptr()).bit_rate }
@@ -47,6 +92,24 @@ impl_for_many! {
             unsafe { (*self.as_ptr()).level as i32 }
         }
 
+        /// Human-readable name of the profile, e.g. `Main 10`.
+        pub fn profile_name(&self) -> Option<&'static str> {
+            self.id().profile_name(self.profile())
+        }
+
+        /// Human-readable name of the level, e.g. `4.1`.
+        pub fn level_name(&self) -> Option<String> {
+            level::name(self.id(), self.level())
+        }
+
+        /// The RFC 6381 codec string, e.g. `avc1.640028` or `mp4a.40.2`, as
+        /// used in HLS playlists and DASH manifests.
+        ///
+        /// Only H.264, HEVC, AV1, AAC, MP3, AC-3 and E-AC-3 are supported.
+        pub fn codec_string(&self) -> Option<String> {
+            unsafe { rfc6381::from_raw(self.as_ptr()) }
+        }
+
         /// Video only
         pub fn width(&self) -> u32 {
             unsafe { (*self.as_ptr()).width as u32 }
@@ -144,3 +207,22 @@ impl_for_many! {
         }
     }
 }
+
+impl_for_many! {
+    impl for Parameters, ParametersMut<'p> {
+        pub fn set_codec_tag(&mut self, value: u32) {
+            unsafe {
+                (*self.as_mut_ptr()).codec_tag = value;
+            }
+        }
+
+        /// Replaces the extradata with a padded copy of `value`, or removes it
+        /// if `value` is empty.
+        pub fn set_extradata(&mut self, value: &[u8]) -> Result<(), Error> {
+            unsafe {
+                let par = self.as_mut_ptr();
+                extradata::set(&mut (*par).extradata, &mut (*par).extradata_size, value)
+            }
+        }
+    }
+}
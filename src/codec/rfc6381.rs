@@ -0,0 +1,223 @@
+//! Codec strings as used in the `codecs` parameter of MIME types, HLS
+//! playlists and DASH manifests, see RFC 6381.
+
+use std::fmt::Write;
+use std::slice;
+
+use super::{nal, Id};
+use crate::ffi::*;
+
+// FFmpeg's AV_PROFILE_UNKNOWN and AV_LEVEL_UNKNOWN.
+const UNKNOWN: i32 = -99;
+
+// Constraint bits of the H.264 profiles, see AV_PROFILE_H264_CONSTRAINED and
+// AV_PROFILE_H264_INTRA.
+const H264_CONSTRAINED: i32 = 1 << 9;
+const H264_INTRA: i32 = 1 << 11;
+
+/// The codec string of a stream, e.g. `avc1.640028` or `mp4a.40.2`.
+///
+/// The profile and level are read from the decoder configuration in
+/// `extradata`, either in the ISO BMFF form (`avcC`, `hvcC`, `av1C`, an
+/// AudioSpecificConfig) or as H.264/HEVC parameter sets in Annex B form,
+/// and are taken from `par` otherwise.
+pub(crate) unsafe fn from_raw(par: *const AVCodecParameters) -> Option<String> {
+    let par = &*par;
+
+    let extradata = if par.extradata.is_null() || par.extradata_size <= 0 {
+        &[][..]
+    } else {
+        slice::from_raw_parts(par.extradata, par.extradata_size as usize)
+    };
+
+    let (profile, level) = (par.profile, par.level);
+
+    match Id::from(par.codec_id) {
+        Id::H264 => avc(extradata, profile, level),
+        Id::HEVC => {
+            let progressive = par.field_order == AVFieldOrder::PROGRESSIVE;
+            hevc(extradata, profile, level, progressive)
+        }
+        Id::AV1 => av1(extradata, profile, level, par.bits_per_raw_sample),
+        Id::AAC => Some(aac(extradata, profile)),
+        Id::MP3 => Some(String::from("mp4a.40.34")),
+        Id::AC3 => Some(String::from("ac-3")),
+        Id::EAC3 => Some(String::from("ec-3")),
+
+        _ => None,
+    }
+}
+
+fn avc(extradata: &[u8], profile: i32, level: i32) -> Option<String> {
+    let (profile_idc, compatibility, level_idc) = if let Some(config) = record(extradata, 4) {
+        (config[1], config[2], config[3])
+    } else if let Some(sps) = parameter_set(extradata, |nal| nal[0] & 0x1f == 7, 4) {
+        (sps[1], sps[2], sps[3])
+    } else if profile != UNKNOWN && level > 0 {
+        let mut compatibility = 0;
+
+        if profile & H264_CONSTRAINED != 0 {
+            compatibility |= 0x40;
+        }
+
+        if profile & H264_INTRA != 0 {
+            compatibility |= 0x10;
+        }
+
+        (profile as u8, compatibility, level as u8)
+    } else {
+        return None;
+    };
+
+    Some(format!(
+        "avc1.{:02X}{:02X}{:02X}",
+        profile_idc, compatibility, level_idc
+    ))
+}
+
+fn hevc(extradata: &[u8], profile: i32, level: i32, progressive: bool) -> Option<String> {
+    // The general profile_tier_level() fields start at byte 1 of an hvcC box,
+    // and after the 2-byte NAL unit header and one byte in an SPS.
+    let ptl = if let Some(config) = record(extradata, 13) {
+        config[1..13].to_vec()
+    } else if let Some(sps) = parameter_set(extradata, |nal| (nal[0] >> 1) & 0x3f == 33, 15) {
+        sps[3..15].to_vec()
+    } else if profile > 0 && profile < 32 && level > 0 {
+        let mut compatibility = 1u32 << (31 - profile);
+
+        // Main decoders can decode Main 10 streams.
+        if profile == 1 {
+            compatibility |= 1 << 29;
+        }
+
+        let mut ptl = vec![profile as u8];
+        ptl.extend_from_slice(&compatibility.to_be_bytes());
+        ptl.extend_from_slice(&[if progressive { 0x90 } else { 0 }, 0, 0, 0, 0, 0]);
+        ptl.push(level as u8);
+
+        ptl
+    } else {
+        return None;
+    };
+
+    let space = ["", "A", "B", "C"][usize::from(ptl[0] >> 6)];
+    let tier = if ptl[0] & 0x20 != 0 { 'H' } else { 'L' };
+    let compatibility = u32::from_be_bytes([ptl[1], ptl[2], ptl[3], ptl[4]]);
+
+    let mut codec = format!(
+        "hvc1.{}{}.{:X}.{}{}",
+        space,
+        ptl[0] & 0x1f,
+        compatibility.reverse_bits(),
+        tier,
+        ptl[11]
+    );
+
+    let constraints = &ptl[5..11];
+    let len = constraints
+        .iter()
+        .rposition(|&b| b != 0)
+        .map_or(0, |i| i + 1);
+
+    for byte in &constraints[..len] {
+        let _ = write!(codec, ".{:X}", byte);
+    }
+
+    Some(codec)
+}
+
+fn av1(extradata: &[u8], profile: i32, level: i32, bits_per_raw_sample: i32) -> Option<String> {
+    let (profile, level, tier, depth) = match extradata {
+        [marker, seq, flags, ..] if marker & 0x80 != 0 => {
+            let depth = match (flags & 0x40 != 0, flags & 0x20 != 0) {
+                (true, true) => 12,
+                (true, false) => 10,
+                _ => 8,
+            };
+
+            (seq >> 5, seq & 0x1f, flags >> 7, depth)
+        }
+        _ if profile >= 0 && level >= 0 => {
+            let depth = if bits_per_raw_sample > 0 {
+                bits_per_raw_sample
+            } else {
+                8
+            };
+
+            (profile as u8, level as u8, 0, depth)
+        }
+        _ => return None,
+    };
+
+    let tier = if tier != 0 { 'H' } else { 'M' };
+
+    Some(format!(
+        "av01.{}.{:02}{}.{:02}",
+        profile, level, tier, depth
+    ))
+}
+
+fn aac(extradata: &[u8], profile: i32) -> String {
+    // The audio object type is the profile plus one.
+    let object_type = if profile >= 0 {
+        profile + 1
+    } else {
+        match extradata {
+            [first, second, ..] if first >> 3 == 31 => {
+                32 + ((i32::from(first & 0x07) << 3) | i32::from(second >> 5))
+            }
+            [first, ..] => i32::from(first >> 3),
+            [] => 2,
+        }
+    };
+
+    format!("mp4a.40.{}", object_type)
+}
+
+// A decoder configuration record, which starts with a version of 1.
+fn record(extradata: &[u8], len: usize) -> Option<&[u8]> {
+    if extradata.len() >= len && extradata[0] == 1 {
+        Some(extradata)
+    } else {
+        None
+    }
+}
+
+// The first NAL unit accepted by `predicate` in Annex B `data`, without
+// emulation prevention bytes.
+fn parameter_set(data: &[u8], predicate: impl Fn(&[u8]) -> bool, len: usize) -> Option<Vec<u8>> {
+    nal::split_annex_b(data)?
+        .into_iter()
+        .filter(|nal| predicate(nal))
+        .map(nal::unescape)
+        .find(|nal| nal.len() >= len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codec_strings() {
+        let avcc = [1, 0x64, 0x00, 0x28, 0xff];
+        assert_eq!(avc(&avcc, UNKNOWN, UNKNOWN).unwrap(), "avc1.640028");
+
+        let annexb = [0, 0, 0, 1, 0x67, 0x4d, 0x40, 0x1f, 0xac, 0, 0, 1, 0x68];
+        assert_eq!(avc(&annexb, UNKNOWN, UNKNOWN).unwrap(), "avc1.4D401F");
+        assert_eq!(avc(&[], 66 | H264_CONSTRAINED, 30).unwrap(), "avc1.42401E");
+
+        let hvcc = [1, 0x01, 0x60, 0, 0, 0, 0xb0, 0, 0, 0, 0, 0, 93, 0xf0];
+        assert_eq!(
+            hevc(&hvcc, UNKNOWN, UNKNOWN, false).unwrap(),
+            "hvc1.1.6.L93.B0"
+        );
+        assert_eq!(hevc(&[], 1, 93, true).unwrap(), "hvc1.1.6.L93.90");
+
+        let av1c = [0x81, 0x04, 0x0c, 0x00];
+        assert_eq!(av1(&av1c, UNKNOWN, UNKNOWN, 0).unwrap(), "av01.0.04M.08");
+        assert_eq!(av1(&[], 0, 8, 10).unwrap(), "av01.0.08M.10");
+
+        assert_eq!(aac(&[0x12, 0x10], UNKNOWN), "mp4a.40.2");
+        assert_eq!(aac(&[], 4), "mp4a.40.5");
+    }
+}
@@ -0,0 +1,720 @@
+//! Parsing and serialization of the ASS payloads of text subtitles.
+//!
+//! FFmpeg represents every text subtitle as ASS: the codec's `subtitle_header`
+//! holds a [`Header`] and every [`Type::Ass`][super::Type::Ass] rect holds a
+//! single [`Dialogue`] without timing information.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Error;
+
+/// An ASS dialogue line as found in subtitle rects:
+/// `ReadOrder,Layer,Style,Name,MarginL,MarginR,MarginV,Effect,Text`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Dialogue {
+    pub read_order: u32,
+    pub layer: i32,
+    pub style: String,
+    pub name: String,
+    pub margin_l: i32,
+    pub margin_r: i32,
+    pub margin_v: i32,
+    pub effect: String,
+    /// Text including override tags, see [`segments`][Self::segments].
+    pub text: String,
+}
+
+impl Dialogue {
+    /// A dialogue line using the `Default` style and showing `text` as is.
+    pub fn new(text: &str) -> Self {
+        Dialogue {
+            read_order: 0,
+            layer: 0,
+            style: String::from("Default"),
+            name: String::new(),
+            margin_l: 0,
+            margin_r: 0,
+            margin_v: 0,
+            effect: String::new(),
+            text: escape(text),
+        }
+    }
+
+    pub fn parse(line: &str) -> Result<Self, Error> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let mut fields = line.splitn(9, ',');
+        let mut next = || fields.next().ok_or(Error::InvalidData);
+
+        Ok(Dialogue {
+            read_order: number(next()?)?,
+            layer: number(next()?)?,
+            style: next()?.to_owned(),
+            name: next()?.to_owned(),
+            margin_l: number(next()?)?,
+            margin_r: number(next()?)?,
+            margin_v: number(next()?)?,
+            effect: next()?.to_owned(),
+            text: next()?.to_owned(),
+        })
+    }
+
+    /// Splits the text into plain text and override blocks.
+    pub fn segments(&self) -> Vec<Segment<'_>> {
+        segments(&self.text)
+    }
+
+    /// The text with all override tags removed and line breaks resolved.
+    pub fn plain_text(&self) -> String {
+        let mut out = String::new();
+
+        for segment in self.segments() {
+            if let Segment::Text(text) = segment {
+                unescape(text, &mut out, false);
+            }
+        }
+
+        out
+    }
+
+    /// The text as simple HTML, as used by SubRip and WebVTT.
+    ///
+    /// Bold, italic, underline, strikeout and primary color overrides are
+    /// converted to `<b>`, `<i>`, `<u>`, `<s>` and `<font color>`, all other
+    /// override tags are dropped.
+    pub fn html(&self) -> String {
+        let mut out = String::new();
+        // Open elements as (name, opening markup).
+        let mut open: Vec<(&str, String)> = Vec::new();
+
+        for segment in self.segments() {
+            let tags = match segment {
+                Segment::Text(text) => {
+                    unescape(text, &mut out, true);
+                    continue;
+                }
+                Segment::Tags(tags) => tags,
+            };
+
+            for tag in tags {
+                let (name, opening) = match (tag.name, tag.argument) {
+                    ("r", _) => {
+                        close_html(&mut out, &mut open, None);
+                        continue;
+                    }
+                    (name @ ("b" | "i" | "u" | "s"), argument) => {
+                        let enabled = !argument.is_empty() && argument != "0";
+                        (name, enabled.then(|| format!("<{name}>")))
+                    }
+                    ("c" | "1c", argument) => {
+                        let opening = parse_color(argument).map(|bgr| {
+                            let [_, b, g, r] = bgr.to_be_bytes();
+                            format!("<font color=\"#{r:02x}{g:02x}{b:02x}\">")
+                        });
+                        ("font", opening)
+                    }
+                    _ => continue,
+                };
+
+                close_html(&mut out, &mut open, Some(name));
+
+                if let Some(opening) = opening {
+                    out.push_str(&opening);
+                    open.push((name, opening));
+                }
+            }
+        }
+
+        close_html(&mut out, &mut open, None);
+
+        out
+    }
+}
+
+/// Closes the innermost open element called `name`, or all elements if `name`
+/// is `None`. Elements nested inside it are closed and reopened so that the
+/// output stays well-formed.
+fn close_html(out: &mut String, open: &mut Vec<(&str, String)>, name: Option<&str>) {
+    let position = match name {
+        Some(name) => match open.iter().rposition(|(n, _)| *n == name) {
+            Some(position) => position,
+            None => return,
+        },
+        None => 0,
+    };
+
+    for (n, _) in open[position..].iter().rev() {
+        out.push_str("</");
+        out.push_str(n);
+        out.push('>');
+    }
+
+    let reopen = open.split_off(position);
+
+    if name.is_some() {
+        for (n, opening) in reopen.into_iter().skip(1) {
+            out.push_str(&opening);
+            open.push((n, opening));
+        }
+    }
+}
+
+impl FromStr for Dialogue {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Dialogue::parse(s)
+    }
+}
+
+impl fmt::Display for Dialogue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{},{},{},{},{},{}",
+            self.read_order,
+            self.layer,
+            self.style,
+            self.name,
+            self.margin_l,
+            self.margin_r,
+            self.margin_v,
+            self.effect,
+            self.text.replace(['\r', '\n'], "")
+        )
+    }
+}
+
+/// A part of the text of a [`Dialogue`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Segment<'a> {
+    /// Text which may contain `\N`, `\n` and `\h` escapes.
+    Text(&'a str),
+    /// The tags of an override block (`{...}`).
+    Tags(Vec<Tag<'a>>),
+}
+
+/// A single override tag such as `\b1`, `\fnArial` or `\pos(10,20)`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Tag<'a> {
+    pub name: &'a str,
+    /// The argument, with surrounding parentheses removed.
+    pub argument: &'a str,
+}
+
+// Longest names first so that prefixes do not shadow them.
+const TAG_NAMES: &[&str] = &[
+    "xbord", "ybord", "xshad", "yshad", "iclip", "alpha", "blur", "bord", "shad", "fscx", "fscy",
+    "fade", "move", "clip", "fsp", "fad", "fax", "fay", "frx", "fry", "frz", "pos", "org", "pbo",
+    "1c", "2c", "3c", "4c", "1a", "2a", "3a", "4a", "an", "be", "fn", "fs", "fe", "fr", "kf", "ko",
+    "a", "b", "c", "i", "k", "K", "p", "q", "r", "s", "t", "u",
+];
+
+fn segments(text: &str) -> Vec<Segment<'_>> {
+    let mut out = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        match find_block(rest) {
+            Some((start, end)) => {
+                if start > 0 {
+                    out.push(Segment::Text(&rest[..start]));
+                }
+
+                out.push(Segment::Tags(tags(&rest[start + 1..end])));
+                rest = &rest[end + 1..];
+            }
+
+            None => {
+                out.push(Segment::Text(rest));
+                break;
+            }
+        }
+    }
+
+    out
+}
+
+/// Finds the next unescaped `{`, and its matching `}`.
+fn find_block(text: &str) -> Option<(usize, usize)> {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'{' => return text[i..].find('}').map(|end| (i, i + end)),
+            _ => i += 1,
+        }
+    }
+
+    None
+}
+
+fn tags(block: &str) -> Vec<Tag<'_>> {
+    let mut out = Vec::new();
+    let mut depth = 0;
+    let mut start = None;
+
+    for (i, c) in block.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            '\\' if depth <= 0 => {
+                if let Some(s) = start {
+                    out.extend(tag(&block[s..i]));
+                }
+                start = Some(i + 1);
+            }
+            _ => (),
+        }
+    }
+
+    if let Some(s) = start {
+        out.extend(tag(&block[s..]));
+    }
+
+    out
+}
+
+fn tag(raw: &str) -> Option<Tag<'_>> {
+    let raw = raw.trim();
+
+    if raw.is_empty() {
+        return None;
+    }
+
+    let length = match TAG_NAMES.iter().find(|name| raw.starts_with(**name)) {
+        Some(name) => name.len(),
+        None => raw
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(raw.len()),
+    };
+
+    let argument = raw[length..].trim();
+    let argument = argument
+        .strip_prefix('(')
+        .map(|a| a.strip_suffix(')').unwrap_or(a))
+        .unwrap_or(argument);
+
+    Some(Tag {
+        name: &raw[..length],
+        argument,
+    })
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '\r' => (),
+            '\n' => out.push_str("\\N"),
+            '{' => out.push_str("\\{"),
+            '}' => out.push_str("\\}"),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+fn unescape(text: &str, out: &mut String, html: bool) {
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let c = match (c, chars.peek()) {
+            ('\\', Some('N' | 'n')) => {
+                chars.next();
+                out.push_str(if html { "<br>" } else { "\n" });
+                continue;
+            }
+            ('\\', Some('h')) => {
+                chars.next();
+                '\u{a0}'
+            }
+            ('\\', Some(&escaped @ ('{' | '}'))) => {
+                chars.next();
+                escaped
+            }
+            (c, _) => c,
+        };
+
+        match c {
+            '<' if html => out.push_str("&lt;"),
+            '>' if html => out.push_str("&gt;"),
+            '&' if html => out.push_str("&amp;"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn number<T: FromStr>(value: &str) -> Result<T, Error> {
+    value.trim().parse().map_err(|_| Error::InvalidData)
+}
+
+fn float(value: &str) -> Result<f64, Error> {
+    number(value)
+}
+
+/// Parses an ASS color like `&H00FFFFFF&` (`AABBGGRR`) or a decimal value.
+pub fn parse_color(value: &str) -> Option<u32> {
+    let value = value.trim().trim_end_matches('&');
+
+    match value
+        .strip_prefix("&H")
+        .or_else(|| value.strip_prefix("&h"))
+    {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => value.parse::<i64>().ok().map(|v| v as u32),
+    }
+}
+
+fn format_color(value: u32) -> String {
+    format!("&H{value:08X}")
+}
+
+/// Metadata of the `[Script Info]` section.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ScriptInfo {
+    pub title: Option<String>,
+    pub script_type: Option<String>,
+    pub play_res_x: Option<u32>,
+    pub play_res_y: Option<u32>,
+    pub wrap_style: Option<u32>,
+    pub scaled_border_and_shadow: Option<bool>,
+    /// All other entries, in order of appearance.
+    pub other: Vec<(String, String)>,
+}
+
+impl ScriptInfo {
+    fn set(&mut self, key: &str, value: &str) {
+        let value = value.trim();
+
+        match key {
+            "Title" => self.title = Some(value.to_owned()),
+            "ScriptType" => self.script_type = Some(value.to_owned()),
+            "PlayResX" => self.play_res_x = value.parse().ok(),
+            "PlayResY" => self.play_res_y = value.parse().ok(),
+            "WrapStyle" => self.wrap_style = value.parse().ok(),
+            "ScaledBorderAndShadow" => {
+                self.scaled_border_and_shadow = Some(value.eq_ignore_ascii_case("yes"))
+            }
+            _ => self.other.push((key.to_owned(), value.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for ScriptInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[Script Info]\r\n")?;
+
+        if let Some(title) = &self.title {
+            write!(f, "Title: {title}\r\n")?;
+        }
+
+        if let Some(script_type) = &self.script_type {
+            write!(f, "ScriptType: {script_type}\r\n")?;
+        }
+
+        if let Some(value) = self.play_res_x {
+            write!(f, "PlayResX: {value}\r\n")?;
+        }
+
+        if let Some(value) = self.play_res_y {
+            write!(f, "PlayResY: {value}\r\n")?;
+        }
+
+        if let Some(value) = self.wrap_style {
+            write!(f, "WrapStyle: {value}\r\n")?;
+        }
+
+        if let Some(value) = self.scaled_border_and_shadow {
+            let value = if value { "yes" } else { "no" };
+            write!(f, "ScaledBorderAndShadow: {value}\r\n")?;
+        }
+
+        for (key, value) in &self.other {
+            write!(f, "{key}: {value}\r\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A style of the `[V4+ Styles]` section.
+///
+/// Colors are `AABBGGRR`, with an alpha of 0 being opaque.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Style {
+    pub name: String,
+    pub font_name: String,
+    pub font_size: f64,
+    pub primary_color: u32,
+    pub secondary_color: u32,
+    pub outline_color: u32,
+    pub back_color: u32,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strike_out: bool,
+    pub scale_x: f64,
+    pub scale_y: f64,
+    pub spacing: f64,
+    pub angle: f64,
+    pub border_style: i32,
+    pub outline: f64,
+    pub shadow: f64,
+    pub alignment: i32,
+    pub margin_l: i32,
+    pub margin_r: i32,
+    pub margin_v: i32,
+    pub encoding: i32,
+}
+
+impl Style {
+    const FORMAT: &'static str = "Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, \
+        OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, \
+        Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding";
+
+    /// Parses the value of a `Style:` line whose fields are ordered by `format`,
+    /// the value of the section's `Format:` line.
+    pub fn parse(format: &str, line: &str) -> Result<Self, Error> {
+        let names = format.split(',').map(str::trim);
+        let values = line.split(',');
+        let mut style = Style::default();
+
+        for (name, value) in names.zip(values) {
+            let flag = |value: &str| number::<i32>(value).map(|v| v != 0);
+
+            match name {
+                "Name" => style.name = value.trim().to_owned(),
+                "Fontname" => style.font_name = value.trim().to_owned(),
+                "Fontsize" => style.font_size = float(value)?,
+                "PrimaryColour" => style.primary_color = color(value)?,
+                "SecondaryColour" => style.secondary_color = color(value)?,
+                "OutlineColour" | "TertiaryColour" => style.outline_color = color(value)?,
+                "BackColour" => style.back_color = color(value)?,
+                "Bold" => style.bold = flag(value)?,
+                "Italic" => style.italic = flag(value)?,
+                "Underline" => style.underline = flag(value)?,
+                "StrikeOut" => style.strike_out = flag(value)?,
+                "ScaleX" => style.scale_x = float(value)?,
+                "ScaleY" => style.scale_y = float(value)?,
+                "Spacing" => style.spacing = float(value)?,
+                "Angle" => style.angle = float(value)?,
+                "BorderStyle" => style.border_style = number(value)?,
+                "Outline" => style.outline = float(value)?,
+                "Shadow" => style.shadow = float(value)?,
+                "Alignment" => style.alignment = number(value)?,
+                "MarginL" => style.margin_l = number(value)?,
+                "MarginR" => style.margin_r = number(value)?,
+                "MarginV" => style.margin_v = number(value)?,
+                "Encoding" => style.encoding = number(value)?,
+                _ => (),
+            }
+        }
+
+        Ok(style)
+    }
+}
+
+fn color(value: &str) -> Result<u32, Error> {
+    parse_color(value).ok_or(Error::InvalidData)
+}
+
+/// Same values as the default style generated by FFmpeg.
+impl Default for Style {
+    fn default() -> Self {
+        Style {
+            name: String::from("Default"),
+            font_name: String::from("Arial"),
+            font_size: 16.0,
+            primary_color: 0x00FFFFFF,
+            secondary_color: 0x00FFFFFF,
+            outline_color: 0x00000000,
+            back_color: 0x00000000,
+            bold: false,
+            italic: false,
+            underline: false,
+            strike_out: false,
+            scale_x: 100.0,
+            scale_y: 100.0,
+            spacing: 0.0,
+            angle: 0.0,
+            border_style: 1,
+            outline: 1.0,
+            shadow: 0.0,
+            alignment: 2,
+            margin_l: 10,
+            margin_r: 10,
+            margin_v: 10,
+            encoding: 1,
+        }
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Style: {},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.name,
+            self.font_name,
+            self.font_size,
+            format_color(self.primary_color),
+            format_color(self.secondary_color),
+            format_color(self.outline_color),
+            format_color(self.back_color),
+            -i32::from(self.bold),
+            -i32::from(self.italic),
+            -i32::from(self.underline),
+            -i32::from(self.strike_out),
+            self.scale_x,
+            self.scale_y,
+            self.spacing,
+            self.angle,
+            self.border_style,
+            self.outline,
+            self.shadow,
+            self.alignment,
+            self.margin_l,
+            self.margin_r,
+            self.margin_v,
+            self.encoding
+        )
+    }
+}
+
+/// The ASS header of a text subtitle codec, see
+/// [`codec::Context::subtitle_header`][crate::codec::Context::subtitle_header].
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Header {
+    pub script_info: ScriptInfo,
+    pub styles: Vec<Style>,
+}
+
+impl Header {
+    pub fn parse(header: &str) -> Result<Self, Error> {
+        let mut out = Header::default();
+        let mut section = "";
+        let mut format = Style::FORMAT.to_owned();
+
+        for line in header.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with(';') || line.starts_with("!:") {
+                continue;
+            }
+
+            if line.starts_with('[') && line.ends_with(']') {
+                section = line;
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+
+            match (section, key.trim()) {
+                ("[Script Info]", key) => out.script_info.set(key, value),
+                ("[V4+ Styles]" | "[V4 Styles]", "Format") => format = value.to_owned(),
+                ("[V4+ Styles]" | "[V4 Styles]", "Style") => {
+                    out.styles.push(Style::parse(&format, value)?)
+                }
+                _ => (),
+            }
+        }
+
+        Ok(out)
+    }
+
+    pub fn style(&self, name: &str) -> Option<&Style> {
+        self.styles.iter().find(|style| style.name == name)
+    }
+}
+
+impl FromStr for Header {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Header::parse(s)
+    }
+}
+
+impl fmt::Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\r\n", self.script_info)?;
+
+        write!(f, "[V4+ Styles]\r\nFormat: {}\r\n", Style::FORMAT)?;
+        for style in &self.styles {
+            write!(f, "{style}\r\n")?;
+        }
+
+        write!(
+            f,
+            "\r\n[Events]\r\n\
+             Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\r\n"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dialogue() {
+        let line = "3,0,Default,Bob,0,0,0,,{\\i1}Hello{\\i0}\\Nworld {\\pos(10,20)\\c&H0000FF&}<3";
+        let dialogue = Dialogue::parse(line).unwrap();
+
+        assert_eq!(dialogue.read_order, 3);
+        assert_eq!(dialogue.name, "Bob");
+        assert_eq!(dialogue.to_string(), line);
+        assert_eq!(dialogue.plain_text(), "Hello\nworld <3");
+        assert_eq!(
+            dialogue.html(),
+            "<i>Hello</i><br>world <font color=\"#ff0000\">&lt;3</font>"
+        );
+
+        let Segment::Tags(tags) = &dialogue.segments()[4] else {
+            panic!("expected override block");
+        };
+        assert_eq!(
+            tags[0],
+            Tag {
+                name: "pos",
+                argument: "10,20"
+            }
+        );
+        assert_eq!(
+            tags[1],
+            Tag {
+                name: "c",
+                argument: "&H0000FF&"
+            }
+        );
+    }
+
+    #[test]
+    fn escape_new() {
+        let dialogue = Dialogue::new("a {b}\nc");
+        assert_eq!(dialogue.text, "a \\{b\\}\\Nc");
+        assert_eq!(dialogue.plain_text(), "a {b}\nc");
+    }
+
+    #[test]
+    fn header_round_trip() {
+        let header = Header {
+            script_info: ScriptInfo {
+                script_type: Some("v4.00+".into()),
+                play_res_x: Some(384),
+                play_res_y: Some(288),
+                ..Default::default()
+            },
+            styles: vec![Style::default()],
+        };
+
+        let parsed = Header::parse(&header.to_string()).unwrap();
+        assert_eq!(parsed, header);
+        assert_eq!(parsed.style("Default").unwrap().primary_color, 0x00FFFFFF);
+    }
+}
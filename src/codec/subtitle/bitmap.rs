@@ -0,0 +1,98 @@
+use std::slice;
+
+/// Indexed bitmap of a [`Type::Bitmap`][super::Type::Bitmap] subtitle rect,
+/// as produced by PGS, DVB and DVD subtitle decoders.
+///
+/// Every pixel is an index into the [`palette`][Self::palette], whose entries
+/// are 32-bit ARGB values in native endianness.
+#[derive(Debug, Clone, Copy)]
+pub struct Bitmap<'a> {
+    width: u32,
+    height: u32,
+    stride: usize,
+    pixels: &'a [u8],
+    palette: &'a [u32],
+}
+
+impl<'a> Bitmap<'a> {
+    /// # Safety
+    /// `pixels` must point to at least `stride * height` bytes and `palette` to
+    /// at least `colors` entries. Both must be valid for the lifetime `'a`.
+    pub unsafe fn from_raw(
+        width: u32,
+        height: u32,
+        stride: usize,
+        pixels: *const u8,
+        palette: *const u32,
+        colors: usize,
+    ) -> Self {
+        let palette = if palette.is_null() {
+            &[]
+        } else {
+            slice::from_raw_parts(palette, colors)
+        };
+
+        Bitmap {
+            width,
+            height,
+            stride,
+            pixels: slice::from_raw_parts(pixels, stride * height as usize),
+            palette,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Number of bytes between the start of two rows.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// All palette indices, including the padding at the end of every row.
+    pub fn pixels(&self) -> &'a [u8] {
+        self.pixels
+    }
+
+    /// The palette indices of row `y`, without padding.
+    pub fn row(&self, y: u32) -> &'a [u8] {
+        if y >= self.height {
+            panic!("out of bounds");
+        }
+
+        let start = y as usize * self.stride;
+        &self.pixels[start..start + self.width as usize]
+    }
+
+    /// Iterates over all rows, without padding.
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// The palette, as 32-bit ARGB values.
+    pub fn palette(&self) -> &'a [u32] {
+        self.palette
+    }
+
+    /// The color of the pixel at (`x`, `y`) as `[r, g, b, a]`.
+    ///
+    /// Indices outside of the palette are treated as fully transparent.
+    pub fn rgba(&self, x: u32, y: u32) -> [u8; 4] {
+        let index = self.row(y)[x as usize] as usize;
+
+        self.palette
+            .get(index)
+            .map_or([0; 4], |&argb| argb_to_rgba(argb))
+    }
+}
+
+#[inline]
+pub(crate) fn argb_to_rgba(argb: u32) -> [u8; 4] {
+    let [a, r, g, b] = argb.to_be_bytes();
+    [r, g, b, a]
+}
@@ -0,0 +1,81 @@
+use libc::EINVAL;
+
+use super::Subtitle;
+use crate::codec::{decoder, encoder, Context, Id};
+use crate::ffi::*;
+use crate::{packet, AsPtr, Error, Packet, Rational};
+
+/// Transcodes packets between the SubRip, ASS and WebVTT text subtitle
+/// formats.
+///
+/// The ASS header generated by the decoder is handed to the encoder as its
+/// `subtitle_header`, so converting to ASS produces matching styles. For ASS
+/// output, the header ends up in the encoder extradata and has to be copied to
+/// the output stream parameters, e.g. with
+/// `stream.set_parameters(Parameters::from(converter.encoder()))`.
+pub struct Converter {
+    decoder: decoder::Subtitle,
+    encoder: encoder::subtitle::Encoder,
+}
+
+impl Converter {
+    /// Creates a converter for packets of a stream with the given codec
+    /// `parameters` and `time_base`, producing packets in the `to` format
+    /// with the same time base.
+    pub fn new<P: AsPtr<AVCodecParameters>>(
+        parameters: P,
+        time_base: Rational,
+        to: Id,
+    ) -> Result<Self, Error> {
+        let mut decoder = Context::from_parameters(parameters)?.decoder();
+        let from = decoder.id();
+
+        if !is_supported(from) || !is_supported(to) {
+            return Err(Error::Other { errno: EINVAL });
+        }
+
+        decoder.set_packet_time_base(time_base);
+        let decoder = decoder.open_as(from)?.subtitle()?;
+
+        let codec = encoder::find(to).ok_or(Error::EncoderNotFound)?;
+        let mut encoder = Context::new_with_codec(codec).encoder().subtitle()?;
+        encoder.set_time_base(time_base);
+
+        if let Some(header) = decoder.subtitle_header() {
+            encoder.set_subtitle_header(header)?;
+        }
+
+        let encoder = encoder.open_as(codec)?;
+
+        Ok(Converter { decoder, encoder })
+    }
+
+    pub fn decoder(&self) -> &decoder::Subtitle {
+        &self.decoder
+    }
+
+    pub fn encoder(&self) -> &encoder::subtitle::Encoder {
+        &self.encoder
+    }
+
+    /// Converts a single packet.
+    ///
+    /// Returns `None` if the packet did not contain a subtitle. The output
+    /// packet keeps the stream index of the input packet.
+    pub fn convert<P: packet::Ref>(&mut self, packet: &P) -> Result<Option<Packet>, Error> {
+        let mut subtitle = Subtitle::new();
+
+        if !self.decoder.decode(packet, &mut subtitle)? {
+            return Ok(None);
+        }
+
+        let mut out = self.encoder.encode_to_packet(&subtitle)?;
+        out.set_stream(unsafe { (*packet.as_ptr()).stream_index as usize });
+
+        Ok(Some(out))
+    }
+}
+
+fn is_supported(id: Id) -> bool {
+    matches!(id, Id::SUBRIP | Id::SRT | Id::ASS | Id::SSA | Id::WEBVTT)
+}
@@ -0,0 +1,133 @@
+use super::{RectRef, Subtitle, Type};
+use crate::Error;
+use libc::ENOMEM;
+
+/// Owned copy of a subtitle rect.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Rect {
+    /// Indexed bitmap, see [`Bitmap`][super::Bitmap].
+    ///
+    /// `pixels` holds `width * height` palette indices without row padding.
+    Bitmap {
+        x: usize,
+        y: usize,
+        width: u32,
+        height: u32,
+        pixels: Vec<u8>,
+        palette: Vec<u32>,
+    },
+    Text(String),
+    /// An ASS `Dialogue` event line.
+    Ass(String),
+}
+
+impl Rect {
+    /// Copies `rect`, returns `None` for rects without content.
+    pub fn from_ref(rect: &RectRef) -> Option<Self> {
+        match rect.kind() {
+            Type::Bitmap => {
+                let bitmap = rect.bitmap()?;
+
+                Some(Rect::Bitmap {
+                    x: rect.x(),
+                    y: rect.y(),
+                    width: bitmap.width(),
+                    height: bitmap.height(),
+                    pixels: bitmap.rows().flatten().copied().collect(),
+                    palette: bitmap.palette().to_vec(),
+                })
+            }
+
+            Type::Text => rect.text().map(|text| Rect::Text(text.to_owned())),
+            Type::Ass => rect.ass().map(|ass| Rect::Ass(ass.to_owned())),
+            Type::None => None,
+        }
+    }
+}
+
+/// A decoded subtitle whose rects are owned, detached from any decoder.
+///
+/// Timestamps are in `AV_TIME_BASE` units (microseconds).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SubtitleEvent {
+    pub start: i64,
+    /// `None` if the end is not known yet, e.g. for bitmap subtitles which are
+    /// cleared by a later event.
+    pub end: Option<i64>,
+    pub rects: Vec<Rect>,
+}
+
+impl SubtitleEvent {
+    /// Copies `subtitle`, returns `None` if it has no pts.
+    pub fn from_subtitle(subtitle: &Subtitle) -> Option<Self> {
+        subtitle.pts().map(|pts| Self::with_pts(subtitle, pts))
+    }
+
+    /// Copies `subtitle`, using `pts` (in `AV_TIME_BASE` units) instead of its
+    /// own pts.
+    pub fn with_pts(subtitle: &Subtitle, pts: i64) -> Self {
+        let start = pts + i64::from(subtitle.start()) * 1000;
+        let end = match subtitle.end() {
+            u32::MAX => None,
+            end => Some(pts + i64::from(end) * 1000),
+        };
+
+        SubtitleEvent {
+            start,
+            end,
+            rects: subtitle
+                .rects()
+                .filter_map(|rect| Rect::from_ref(&rect))
+                .collect(),
+        }
+    }
+
+    /// Converts the event back into a [`Subtitle`], ready to be encoded.
+    pub fn to_subtitle(&self) -> Result<Subtitle, Error> {
+        let mut subtitle = Subtitle::new();
+        subtitle.set_pts(Some(self.start));
+        subtitle.set_start(0);
+        subtitle.set_end(match self.end {
+            Some(end) => ((end - self.start).max(0) / 1000) as u32,
+            None => u32::MAX,
+        });
+
+        for rect in &self.rects {
+            match rect {
+                Rect::Bitmap {
+                    x,
+                    y,
+                    width,
+                    height,
+                    pixels,
+                    palette,
+                } => {
+                    let mut out = subtitle
+                        .add_rect(Type::Bitmap)
+                        .ok_or(Error::Other { errno: ENOMEM })?;
+                    out.set_x(*x);
+                    out.set_y(*y);
+                    out.set_width(*width);
+                    out.set_height(*height);
+                    out.set_bitmap(pixels, palette)?;
+                }
+
+                Rect::Text(text) => {
+                    let mut out = subtitle
+                        .add_rect(Type::Text)
+                        .ok_or(Error::Other { errno: ENOMEM })?;
+                    out.set_text(text);
+                }
+
+                Rect::Ass(ass) => {
+                    let mut out = subtitle
+                        .add_rect(Type::Ass)
+                        .ok_or(Error::Other { errno: ENOMEM })?;
+                    out.set_ass(ass);
+                }
+            }
+        }
+
+        Ok(subtitle)
+    }
+}
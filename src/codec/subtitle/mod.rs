@@ -1,6 +1,17 @@
 pub mod flag;
 pub use self::flag::Flags;
 
+pub mod ass;
+
+mod bitmap;
+pub use self::bitmap::Bitmap;
+
+mod event;
+pub use self::event::{Rect, SubtitleEvent};
+
+mod converter;
+pub use self::converter::Converter;
+
 mod rect;
 mod rect_common;
 pub use self::rect::RectRef;
@@ -12,7 +23,10 @@ use std::mem;
 use std::ptr::NonNull;
 
 use crate::ffi::*;
-use libc::size_t;
+use crate::frame;
+use crate::util::format::Pixel;
+use crate::{AsPtr, Error};
+use libc::{size_t, EINVAL};
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
 
@@ -53,6 +67,10 @@ impl From<Type> for AVSubtitleType {
     }
 }
 
+/// A decoded subtitle event.
+///
+/// The subtitle owns its rects, which are released with `avsubtitle_free`
+/// when it is dropped.
 pub struct Subtitle(AVSubtitle);
 
 impl Subtitle {
@@ -153,6 +171,83 @@ impl Subtitle {
     }
 }
 
+impl Subtitle {
+    /// Composites all bitmap rects onto `frame`, which must be in
+    /// [`Pixel::RGBA`] format.
+    ///
+    /// Rect positions are relative to the video the subtitle was decoded for,
+    /// so `frame` should normally have the size of that video. Parts of rects
+    /// outside of the frame are clipped, other rect types are ignored.
+    /// Returns `EINVAL` without rendering anything if a rect has a negative
+    /// position.
+    pub fn render_rgba(&self, frame: &mut frame::Video) -> Result<(), Error> {
+        if frame.format() != Pixel::RGBA {
+            return Err(Error::Other { errno: EINVAL });
+        }
+
+        let negative = self
+            .rects()
+            .any(|rect| unsafe { (*rect.as_ptr()).x < 0 || (*rect.as_ptr()).y < 0 });
+
+        if negative {
+            return Err(Error::Other { errno: EINVAL });
+        }
+
+        let frame_width = frame.width() as usize;
+        let frame_height = frame.height() as usize;
+        let stride = frame.stride(0);
+        let data = frame.data_mut(0);
+
+        for rect in self.rects() {
+            let Some(bitmap) = rect.bitmap() else {
+                continue;
+            };
+
+            let (x0, y0) = (rect.x(), rect.y());
+            let width = (bitmap.width() as usize).min(frame_width.saturating_sub(x0));
+            let height = (bitmap.height() as usize).min(frame_height.saturating_sub(y0));
+
+            if width == 0 || height == 0 {
+                continue;
+            }
+
+            for (y, row) in bitmap.rows().take(height).enumerate() {
+                let start = (y0 + y) * stride + x0 * 4;
+                let out = &mut data[start..start + width * 4];
+
+                for (dst, &index) in out.chunks_exact_mut(4).zip(row) {
+                    if let Some(&argb) = bitmap.palette().get(index as usize) {
+                        blend(dst, bitmap::argb_to_rgba(argb));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Source-over blending of a straight-alpha `src` pixel onto `dst`.
+fn blend(dst: &mut [u8], src: [u8; 4]) {
+    let src_a = u32::from(src[3]);
+
+    match src_a {
+        0 => {}
+        255 => dst.copy_from_slice(&src),
+        _ => {
+            let dst_a = u32::from(dst[3]) * (255 - src_a) / 255;
+            let out_a = src_a + dst_a;
+
+            for c in 0..3 {
+                let value = (u32::from(src[c]) * src_a + u32::from(dst[c]) * dst_a) / out_a;
+                dst[c] = value as u8;
+            }
+
+            dst[3] = out_a as u8;
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RectIter<'s> {
     raw_iter: std::slice::Iter<'s, *mut AVSubtitleRect>,
@@ -241,8 +336,110 @@ impl<'s> DoubleEndedIterator for RectMutIter<'s> {
 impl<'s> ExactSizeIterator for RectMutIter<'s> {}
 impl<'s> FusedIterator for RectMutIter<'s> {}
 
+impl Drop for Subtitle {
+    fn drop(&mut self) {
+        unsafe {
+            avsubtitle_free(self.as_mut_ptr());
+        }
+    }
+}
+
 impl Default for Subtitle {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AsMutPtr;
+
+    #[test]
+    fn render_bitmap_rect() {
+        let mut subtitle = Subtitle::new();
+
+        {
+            let mut rect = subtitle.add_rect(Type::Bitmap).unwrap();
+            rect.set_x(1);
+            rect.set_y(1);
+            rect.set_width(2);
+            rect.set_height(2);
+            rect.set_bitmap(&[0, 1, 1, 0], &[0x00000000, 0xFFFF0000])
+                .unwrap();
+        }
+
+        let rect = subtitle.rects().next().unwrap();
+        let bitmap = rect.bitmap().unwrap();
+        assert_eq!(bitmap.row(0), &[0, 1]);
+        assert_eq!(bitmap.rgba(1, 0), [255, 0, 0, 255]);
+        assert_eq!(bitmap.rgba(0, 0), [0, 0, 0, 0]);
+
+        let mut frame = frame::Video::new(Pixel::RGBA, 4, 4);
+        let stride = frame.stride(0);
+        frame.data_mut(0).fill(0);
+        subtitle.render_rgba(&mut frame).unwrap();
+
+        let data = frame.data(0);
+        assert_eq!(
+            &data[stride + 4..stride + 12],
+            &[0, 0, 0, 0, 255, 0, 0, 255]
+        );
+        assert_eq!(&data[2 * stride + 4..2 * stride + 8], &[255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn render_clipped_rects() {
+        let mut subtitle = Subtitle::new();
+
+        {
+            let mut rect = subtitle.add_rect(Type::Bitmap).unwrap();
+            rect.set_x(10);
+            rect.set_y(3);
+            rect.set_width(2);
+            rect.set_height(1);
+            rect.set_bitmap(&[1, 1], &[0x00000000, 0xFFFF0000]).unwrap();
+        }
+
+        let mut frame = frame::Video::new(Pixel::RGBA, 4, 4);
+        frame.data_mut(0).fill(0);
+        subtitle.render_rgba(&mut frame).unwrap();
+        assert!(frame.data(0).iter().all(|&byte| byte == 0));
+
+        {
+            let mut rect = subtitle.add_rect(Type::Bitmap).unwrap();
+            rect.set_width(1);
+            rect.set_height(1);
+            rect.set_bitmap(&[1], &[0x00000000, 0xFFFF0000]).unwrap();
+            unsafe { (*rect.as_mut_ptr()).x = -1 };
+        }
+
+        assert_eq!(
+            subtitle.render_rgba(&mut frame),
+            Err(Error::Other { errno: EINVAL })
+        );
+    }
+
+    #[test]
+    fn event_round_trip() {
+        let event = SubtitleEvent {
+            start: 1_500_000,
+            end: Some(3_000_000),
+            rects: vec![
+                Rect::Ass("0,0,Default,,0,0,0,,Hello".into()),
+                Rect::Bitmap {
+                    x: 4,
+                    y: 2,
+                    width: 2,
+                    height: 1,
+                    pixels: vec![1, 0],
+                    palette: vec![0, 0xFFFFFFFF],
+                },
+            ],
+        };
+
+        let subtitle = event.to_subtitle().unwrap();
+        assert_eq!(subtitle.end(), 1500);
+        assert_eq!(SubtitleEvent::from_subtitle(&subtitle), Some(event));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::macros::impl_for_many;
 
-use super::{Flags, RectMut, RectRef, Type};
+use super::{ass, Bitmap, Flags, RectMut, RectRef, Type};
 use crate::utils;
 use crate::AsPtr;
 
@@ -35,6 +35,28 @@ impl_for_many! {
             unsafe { (*self.as_ptr()).nb_colors as usize }
         }
 
+        /// The indexed bitmap of a [`Type::Bitmap`] rect.
+        ///
+        /// Returns `None` for other rect types or if no bitmap was set.
+        pub fn bitmap(&self) -> Option<Bitmap<'_>> {
+            unsafe {
+                let rect = self.as_ptr();
+
+                if (*rect).type_ != Type::Bitmap.into() || (*rect).data[0].is_null() {
+                    return None;
+                }
+
+                Some(Bitmap::from_raw(
+                    (*rect).w.max(0) as u32,
+                    (*rect).h.max(0) as u32,
+                    (*rect).linesize[0].max(0) as usize,
+                    (*rect).data[0],
+                    (*rect).data[1] as *const u32,
+                    (*rect).nb_colors.max(0) as usize,
+                ))
+            }
+        }
+
         pub fn text(&self) -> Option<&str> {
             unsafe { utils::optional_str_from_c_ptr((*self.as_ptr()).text) }
         }
@@ -42,5 +64,10 @@ impl_for_many! {
         pub fn ass(&self) -> Option<&str> {
             unsafe { utils::optional_str_from_c_ptr((*self.as_ptr()).ass) }
         }
+
+        /// The parsed [`ass`][Self::ass] dialogue line, if it is valid.
+        pub fn dialogue(&self) -> Option<ass::Dialogue> {
+            self.ass().and_then(|line| ass::Dialogue::parse(line).ok())
+        }
     }
 }
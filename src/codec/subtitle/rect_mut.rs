@@ -1,11 +1,11 @@
-use libc::c_int;
+use libc::{c_int, c_void, EINVAL, ENOMEM};
 use std::ffi::CString;
 use std::marker::PhantomData;
-use std::ptr::NonNull;
+use std::ptr::{self, NonNull};
 
-use super::{Flags, RectRef, Subtitle};
+use super::{ass, Flags, RectRef, Subtitle, Type};
 use crate::ffi::*;
-use crate::{AsMutPtr, AsPtr};
+use crate::{AsMutPtr, AsPtr, Error};
 
 pub struct RectMut<'s> {
     ptr: NonNull<AVSubtitleRect>,
@@ -63,21 +63,82 @@ impl<'s> RectMut<'s> {
         }
     }
 
+    /// Replaces the indexed bitmap of this rect and turns it into a
+    /// [`Type::Bitmap`] rect.
+    ///
+    /// `indices` holds one palette index per pixel, row by row without padding,
+    /// for the current [`width`][Self::width] and [`height`][Self::height], so
+    /// those have to be set first. `palette` holds up to 256 ARGB colors in
+    /// native endianness.
+    pub fn set_bitmap(&mut self, indices: &[u8], palette: &[u32]) -> Result<(), Error> {
+        let width = self.width() as usize;
+        let height = self.height() as usize;
+
+        if indices.len() != width * height
+            || palette.len() > AVPALETTE_COUNT as usize
+            || indices.iter().any(|&i| i as usize >= palette.len())
+        {
+            return Err(Error::Other { errno: EINVAL });
+        }
+
+        unsafe {
+            let pixels = av_malloc(indices.len().max(1)) as *mut u8;
+            // Decoders always allocate a full palette, do the same so that
+            // code reading all AVPALETTE_COUNT entries stays in bounds.
+            let colors = av_mallocz(AVPALETTE_SIZE as usize) as *mut u32;
+
+            if pixels.is_null() || colors.is_null() {
+                av_free(pixels as *mut c_void);
+                av_free(colors as *mut c_void);
+                return Err(Error::Other { errno: ENOMEM });
+            }
+
+            ptr::copy_nonoverlapping(indices.as_ptr(), pixels, indices.len());
+            ptr::copy_nonoverlapping(palette.as_ptr(), colors, palette.len());
+
+            let rect = self.as_mut_ptr();
+
+            av_freep(&mut (*rect).data[0] as *mut *mut u8 as *mut c_void);
+            av_freep(&mut (*rect).data[1] as *mut *mut u8 as *mut c_void);
+
+            (*rect).type_ = Type::Bitmap.into();
+            (*rect).data[0] = pixels;
+            (*rect).data[1] = colors as *mut u8;
+            (*rect).linesize[0] = width as c_int;
+            (*rect).nb_colors = palette.len() as c_int;
+        }
+
+        Ok(())
+    }
+
     pub fn set_text(&mut self, value: &str) {
         let value = CString::new(value).unwrap();
 
         unsafe {
-            (*self.as_mut_ptr()).text = av_strdup(value.as_ptr());
+            let rect = self.as_mut_ptr();
+            av_freep(&mut (*rect).text as *mut *mut _ as *mut c_void);
+            (*rect).text = av_strdup(value.as_ptr());
         }
     }
 
+    /// Sets the ASS dialogue line of this rect.
+    ///
+    /// The line must use the format of [`ass::Dialogue`], which can be used to
+    /// build it safely.
     pub fn set_ass(&mut self, value: &str) {
         let value = CString::new(value).unwrap();
 
         unsafe {
-            (*self.as_mut_ptr()).ass = av_strdup(value.as_ptr());
+            let rect = self.as_mut_ptr();
+            av_freep(&mut (*rect).ass as *mut *mut _ as *mut c_void);
+            (*rect).ass = av_strdup(value.as_ptr());
         }
     }
+
+    /// Sets the ASS dialogue line of this rect from `dialogue`.
+    pub fn set_dialogue(&mut self, dialogue: &ass::Dialogue) {
+        self.set_ass(&dialogue.to_string());
+    }
 }
 
 impl<'s> AsPtr<AVSubtitleRect> for RectMut<'s> {
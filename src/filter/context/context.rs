@@ -76,3 +76,5 @@ impl<'a> AsMutPtr<AVFilterContext> for Context<'a> {
 }
 
 impl<'a> option::Settable<AVFilterContext> for Context<'a> {}
+impl<'a> option::Gettable<AVFilterContext> for Context<'a> {}
+impl<'a> option::Iterable<AVFilterContext> for Context<'a> {}
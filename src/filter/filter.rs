@@ -3,7 +3,7 @@ use std::marker::PhantomData;
 
 use super::{Flags, Pad};
 use crate::ffi::*;
-use crate::utils;
+use crate::{option, utils};
 
 pub struct Filter {
     ptr: *mut AVFilter,
@@ -32,6 +32,11 @@ impl Filter {
         unsafe { utils::optional_str_from_c_ptr((*self.as_ptr()).description) }
     }
 
+    /// Class of the private options of the filter.
+    pub fn priv_class(&self) -> Option<option::Class<'static>> {
+        unsafe { option::Class::from_raw((*self.as_ptr()).priv_class) }
+    }
+
     pub fn inputs(&self) -> Option<PadIter<'_>> {
         unsafe {
             let ptr = (*self.as_ptr()).inputs;
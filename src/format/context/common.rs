@@ -4,7 +4,10 @@ use std::ptr;
 
 use super::destructor::{self, Destructor};
 use crate::ffi::*;
-use crate::{media, Chapter, ChapterMut, DictionaryRef, Stream, StreamMut};
+use crate::{media, Chapter, ChapterMut, DictionaryRef, Program, ProgramMut, Stream, StreamMut};
+
+#[cfg(feature = "ffmpeg_7_0")]
+use crate::format::stream_group::{StreamGroup, StreamGroupMut};
 use libc::{c_int, c_uint};
 
 type DtorHolder = Destructor;
@@ -127,6 +130,84 @@ impl Context {
         ChapterIterMut::new(self)
     }
 
+    #[inline]
+    pub fn nb_programs(&self) -> u32 {
+        unsafe { (*self.as_ptr()).nb_programs }
+    }
+
+    pub fn program<'a, 'b>(&'a self, index: usize) -> Option<Program<'b>>
+    where
+        'a: 'b,
+    {
+        unsafe {
+            if index >= self.nb_programs() as usize {
+                None
+            } else {
+                Some(Program::wrap(self, index))
+            }
+        }
+    }
+
+    pub fn program_mut<'a, 'b>(&'a mut self, index: usize) -> Option<ProgramMut<'b>>
+    where
+        'a: 'b,
+    {
+        unsafe {
+            if index >= self.nb_programs() as usize {
+                None
+            } else {
+                Some(ProgramMut::wrap(self, index))
+            }
+        }
+    }
+
+    pub fn programs(&self) -> ProgramIter<'_> {
+        ProgramIter::new(self)
+    }
+
+    pub fn programs_mut(&mut self) -> ProgramIterMut<'_> {
+        ProgramIterMut::new(self)
+    }
+
+    #[cfg(feature = "ffmpeg_7_0")]
+    #[inline]
+    pub fn nb_stream_groups(&self) -> u32 {
+        unsafe { (*self.as_ptr()).nb_stream_groups }
+    }
+
+    #[cfg(feature = "ffmpeg_7_0")]
+    pub fn stream_group<'a, 'b>(&'a self, index: usize) -> Option<StreamGroup<'b>>
+    where
+        'a: 'b,
+    {
+        unsafe {
+            if index >= self.nb_stream_groups() as usize {
+                None
+            } else {
+                Some(StreamGroup::wrap(self, index))
+            }
+        }
+    }
+
+    #[cfg(feature = "ffmpeg_7_0")]
+    pub fn stream_group_mut<'a, 'b>(&'a mut self, index: usize) -> Option<StreamGroupMut<'b>>
+    where
+        'a: 'b,
+    {
+        unsafe {
+            if index >= self.nb_stream_groups() as usize {
+                None
+            } else {
+                Some(StreamGroupMut::wrap(self, index))
+            }
+        }
+    }
+
+    #[cfg(feature = "ffmpeg_7_0")]
+    pub fn stream_groups(&self) -> StreamGroupIter<'_> {
+        StreamGroupIter::new(self)
+    }
+
     pub fn metadata(&self) -> DictionaryRef<'_> {
         unsafe { DictionaryRef::from_raw((*self.as_ptr()).metadata) }
     }
@@ -387,12 +468,142 @@ impl<'a> Iterator for ChapterIterMut<'a> {
 
 impl<'a> ExactSizeIterator for ChapterIterMut<'a> {}
 
+pub struct ProgramIter<'a> {
+    context: &'a Context,
+    current: c_uint,
+}
+
+impl<'a> ProgramIter<'a> {
+    pub fn new<'s, 'c: 's>(context: &'c Context) -> ProgramIter<'s> {
+        ProgramIter {
+            context,
+            current: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for ProgramIter<'a> {
+    type Item = Program<'a>;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        unsafe {
+            if self.current >= self.context.nb_programs() {
+                return None;
+            }
+
+            self.current += 1;
+
+            Some(Program::wrap(self.context, (self.current - 1) as usize))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let length = self.context.nb_programs() as usize;
+
+        (
+            length - self.current as usize,
+            Some(length - self.current as usize),
+        )
+    }
+}
+
+impl<'a> ExactSizeIterator for ProgramIter<'a> {}
+
+pub struct ProgramIterMut<'a> {
+    context: &'a mut Context,
+    current: c_uint,
+}
+
+impl<'a> ProgramIterMut<'a> {
+    pub fn new<'s, 'c: 's>(context: &'c mut Context) -> ProgramIterMut<'s> {
+        ProgramIterMut {
+            context,
+            current: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for ProgramIterMut<'a> {
+    type Item = ProgramMut<'a>;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.current >= self.context.nb_programs() {
+            return None;
+        }
+        self.current += 1;
+
+        unsafe {
+            Some(ProgramMut::wrap(
+                mem::transmute_copy(&self.context),
+                (self.current - 1) as usize,
+            ))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let length = self.context.nb_programs() as usize;
+
+        (
+            length - self.current as usize,
+            Some(length - self.current as usize),
+        )
+    }
+}
+
+impl<'a> ExactSizeIterator for ProgramIterMut<'a> {}
+
+#[cfg(feature = "ffmpeg_7_0")]
+pub struct StreamGroupIter<'a> {
+    context: &'a Context,
+    current: c_uint,
+}
+
+#[cfg(feature = "ffmpeg_7_0")]
+impl<'a> StreamGroupIter<'a> {
+    pub fn new<'s, 'c: 's>(context: &'c Context) -> StreamGroupIter<'s> {
+        StreamGroupIter {
+            context,
+            current: 0,
+        }
+    }
+}
+
+#[cfg(feature = "ffmpeg_7_0")]
+impl<'a> Iterator for StreamGroupIter<'a> {
+    type Item = StreamGroup<'a>;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        unsafe {
+            if self.current >= self.context.nb_stream_groups() {
+                return None;
+            }
+
+            self.current += 1;
+
+            Some(StreamGroup::wrap(self.context, (self.current - 1) as usize))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let length = self.context.nb_stream_groups() as usize;
+
+        (
+            length - self.current as usize,
+            Some(length - self.current as usize),
+        )
+    }
+}
+
+#[cfg(feature = "ffmpeg_7_0")]
+impl<'a> ExactSizeIterator for StreamGroupIter<'a> {}
+
 impl fmt::Debug for Context {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         let mut s = fmt.debug_struct("AVFormatContext");
         s.field("bit_rate", &self.bit_rate());
         s.field("duration", &self.duration());
         s.field("nb_chapters", &self.nb_chapters());
+        s.field("nb_programs", &self.nb_programs());
         s.field("nb_streams", &self.nb_streams());
         s.finish()
     }
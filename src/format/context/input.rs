@@ -1,11 +1,17 @@
 use std::ffi::CString;
+use std::iter;
 use std::mem;
 use std::ops::{Bound, Deref, DerefMut, RangeBounds};
+use std::ptr;
 
 use super::common::Context;
 use super::destructor;
+use crate::codec::{decoder, subtitle::SubtitleEvent, Context as CodecContext};
 use crate::ffi::*;
-use crate::{format, Error, Packet, Stream};
+use crate::rescale::TIME_BASE;
+use crate::{
+    format, option, AsMutPtr, AsPtr, Dictionary, Error, Packet, Rational, Rescale, Stream, Subtitle,
+};
 
 pub struct Input {
     ptr: *mut AVFormatContext,
@@ -40,10 +46,81 @@ impl Input {
         unsafe { (*self.as_ptr()).probe_score }
     }
 
+    /// Read packets of the input to get stream information.
+    ///
+    /// This is done automatically when opening an input, unless disabled with
+    /// [`InputOptions::find_stream_info`][crate::format::InputOptions::find_stream_info].
+    pub fn find_stream_info(&mut self) -> Result<(), Error> {
+        unsafe {
+            match avformat_find_stream_info(self.as_mut_ptr(), ptr::null_mut()) {
+                r if r >= 0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Read packets of the input to get stream information, using custom
+    /// codec options for each stream.
+    ///
+    /// The `n`-th dictionary in `options` is applied to the codec of the
+    /// `n`-th stream; missing entries are treated as empty dictionaries and
+    /// surplus entries are ignored. Returns the options that were not consumed,
+    /// one dictionary per stream that existed before probing.
+    ///
+    /// A successful return does not mean that every stream could be analyzed.
+    /// Use [`Stream::has_codec_parameters`] to check individual streams.
+    pub fn find_stream_info_with(
+        &mut self,
+        options: Vec<Dictionary>,
+    ) -> Result<Vec<Dictionary>, Error> {
+        let nb_streams = self.nb_streams() as usize;
+
+        let mut options = options
+            .into_iter()
+            .chain(iter::repeat_with(Dictionary::new))
+            .take(nb_streams)
+            .map(Dictionary::into_raw)
+            .collect::<Vec<_>>();
+
+        unsafe {
+            let res = avformat_find_stream_info(self.as_mut_ptr(), options.as_mut_ptr());
+            let options = options
+                .into_iter()
+                .map(|ptr| Dictionary::from_raw(ptr))
+                .collect();
+
+            match res {
+                r if r >= 0 => Ok(options),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
     pub fn packets(&mut self) -> PacketIter<'_> {
         PacketIter::new(self)
     }
 
+    /// Decodes the subtitle stream at `index` into owned events, reading
+    /// packets until the end of the input.
+    ///
+    /// Packets of other streams are skipped.
+    pub fn subtitles(&mut self, index: usize) -> Result<SubtitleEvents<'_>, Error> {
+        let stream = self.stream(index).ok_or(Error::StreamNotFound)?;
+        let time_base = stream.time_base();
+
+        let mut decoder = CodecContext::from_parameters(stream.parameters())?.decoder();
+        decoder.set_packet_time_base(time_base);
+        let decoder = decoder.open()?.subtitle()?;
+
+        Ok(SubtitleEvents {
+            context: self,
+            decoder,
+            index,
+            time_base,
+            eof: false,
+        })
+    }
+
     pub fn pause(&mut self) -> Result<(), Error> {
         unsafe {
             match av_read_pause(self.as_mut_ptr()) {
@@ -84,6 +161,22 @@ impl Input {
     }
 }
 
+impl AsPtr<AVFormatContext> for Input {
+    fn as_ptr(&self) -> *const AVFormatContext {
+        self.ptr as *const _
+    }
+}
+
+impl AsMutPtr<AVFormatContext> for Input {
+    fn as_mut_ptr(&mut self) -> *mut AVFormatContext {
+        self.ptr
+    }
+}
+
+impl option::Settable<AVFormatContext> for Input {}
+impl option::Gettable<AVFormatContext> for Input {}
+impl option::Iterable<AVFormatContext> for Input {}
+
 impl Deref for Input {
     type Target = Context;
 
@@ -129,6 +222,64 @@ impl<'a> Iterator for PacketIter<'a> {
     }
 }
 
+pub struct SubtitleEvents<'a> {
+    context: &'a mut Input,
+    decoder: decoder::Subtitle,
+    index: usize,
+    time_base: Rational,
+    eof: bool,
+}
+
+impl<'a> SubtitleEvents<'a> {
+    fn event(&self, subtitle: &Subtitle, packet: &Packet) -> Option<SubtitleEvent> {
+        let pts = subtitle.pts().or_else(|| {
+            let ts = packet.pts().or(packet.dts())?;
+            Some(ts.rescale(self.time_base, TIME_BASE))
+        })?;
+
+        let mut event = SubtitleEvent::with_pts(subtitle, pts);
+
+        if event.end.is_none() && packet.duration() > 0 {
+            event.end = Some(event.start + packet.duration().rescale(self.time_base, TIME_BASE));
+        }
+
+        Some(event)
+    }
+}
+
+impl<'a> Iterator for SubtitleEvents<'a> {
+    type Item = Result<SubtitleEvent, Error>;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        let mut subtitle = Subtitle::new();
+
+        loop {
+            let mut packet = Packet::empty();
+
+            if !self.eof {
+                match packet.read(self.context) {
+                    Ok(..) if packet.stream() != self.index => continue,
+                    Ok(..) => (),
+                    Err(Error::Eof) => self.eof = true,
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+
+            // After the end of the input, empty packets drain delayed subtitles.
+            match self.decoder.decode(&packet, &mut subtitle) {
+                Ok(true) => {
+                    if let Some(event) = self.event(&subtitle, &packet) {
+                        return Some(Ok(event));
+                    }
+                }
+                Ok(false) if self.eof => return None,
+                Ok(false) => (),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
 pub fn dump(ctx: &Input, index: i32, url: Option<&str>) {
     let url = url.map(|u| CString::new(u).unwrap());
 
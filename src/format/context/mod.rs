@@ -5,7 +5,7 @@ pub mod input;
 pub use self::input::Input;
 
 pub mod output;
-pub use self::output::Output;
+pub use self::output::{Output, WriteError};
 
 #[doc(hidden)]
 pub mod common;
@@ -1,4 +1,7 @@
+use std::error;
 use std::ffi::CString;
+use std::fmt;
+use std::mem;
 use std::ops::{Deref, DerefMut};
 use std::ptr;
 
@@ -6,9 +9,16 @@ use super::common::Context;
 use super::destructor;
 use crate::codec::traits;
 use crate::ffi::*;
+use crate::format::OutputOptions;
+use crate::packet::{Mut, Ref};
 use crate::{
-    format, AsMutPtr, ChapterMut, DictionaryMut, DictionaryRef, Error, Rational, StreamMut,
+    format, option, AsMutPtr, AsPtr, ChapterMut, DictionaryMut, DictionaryRef, Error, Frame,
+    Packet, ProgramMut, Rational, StreamMut,
 };
+use libc::{c_int, c_uint};
+
+#[cfg(feature = "ffmpeg_7_0")]
+use crate::format::stream_group::{self, StreamGroupMut};
 
 pub struct Output {
     ptr: *mut AVFormatContext,
@@ -62,6 +72,127 @@ impl Output {
         }
     }
 
+    /// Apply muxer options controlling interleaving and timestamps.
+    ///
+    /// Must be called before [`write_header`][Self::write_header].
+    pub fn set_options(&mut self, options: OutputOptions) {
+        unsafe {
+            options.apply(self.as_mut_ptr());
+        }
+    }
+
+    /// Write a packet directly to the output, without interleaving.
+    ///
+    /// Errors carry the stream and timestamps of the packet which could not be
+    /// written. Returns `true` if the muxer was flushed and there is no more
+    /// data to flush.
+    pub fn write(&mut self, packet: &Packet) -> Result<bool, WriteError> {
+        let error = WriteError::new(self, packet);
+
+        unsafe {
+            if packet.is_empty() {
+                return Err(error.with(Error::InvalidData));
+            }
+
+            match av_write_frame(self.as_mut_ptr(), packet.as_ptr() as *mut _) {
+                1 => Ok(true),
+                0 => Ok(false),
+                e => Err(error.with(Error::from(e))),
+            }
+        }
+    }
+
+    /// Write a packet to the output, making sure packets are properly interleaved.
+    ///
+    /// The packet is consumed by the muxer and left blank. Errors carry the
+    /// stream and timestamps of the packet which could not be written.
+    pub fn write_interleaved(&mut self, packet: &mut Packet) -> Result<(), WriteError> {
+        let error = WriteError::new(self, packet);
+
+        unsafe {
+            if packet.is_empty() {
+                return Err(error.with(Error::InvalidData));
+            }
+
+            match av_interleaved_write_frame(self.as_mut_ptr(), packet.as_mut_ptr()) {
+                0 => Ok(()),
+                e => Err(error.with(Error::from(e))),
+            }
+        }
+    }
+
+    /// Flush data buffered within the muxer.
+    ///
+    /// Returns `true` if there is no more data to flush.
+    pub fn flush(&mut self) -> Result<bool, Error> {
+        unsafe {
+            match av_write_frame(self.as_mut_ptr(), ptr::null_mut()) {
+                1 => Ok(true),
+                0 => Ok(false),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Whether the muxer accepts uncoded frames for the stream at `stream_index`.
+    pub fn supports_uncoded_frame(&self, stream_index: usize) -> bool {
+        if stream_index >= self.nb_streams() as usize {
+            return false;
+        }
+
+        unsafe { av_write_uncoded_frame_query(self.as_ptr() as *mut _, stream_index as c_int) >= 0 }
+    }
+
+    /// Write a raw, uncoded frame to muxers which support it (e.g. `xv` or `alsa`).
+    ///
+    /// Ownership of the frame is passed to the muxer. If there is no stream at
+    /// `stream_index`, the frame is dropped and [`Error::StreamNotFound`] is returned.
+    pub fn write_uncoded_frame<F: Into<Frame>>(
+        &mut self,
+        stream_index: usize,
+        frame: F,
+    ) -> Result<(), Error> {
+        if stream_index >= self.nb_streams() as usize {
+            return Err(Error::StreamNotFound);
+        }
+
+        unsafe {
+            let frame = frame.into();
+            let ptr = frame.as_ptr() as *mut _;
+            mem::forget(frame);
+
+            match av_write_uncoded_frame(self.as_mut_ptr(), stream_index as c_int, ptr) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Write a raw, uncoded frame, making sure frames are properly interleaved.
+    ///
+    /// See [`write_uncoded_frame`][Self::write_uncoded_frame].
+    pub fn write_uncoded_frame_interleaved<F: Into<Frame>>(
+        &mut self,
+        stream_index: usize,
+        frame: F,
+    ) -> Result<(), Error> {
+        if stream_index >= self.nb_streams() as usize {
+            return Err(Error::StreamNotFound);
+        }
+
+        unsafe {
+            let frame = frame.into();
+            let ptr = frame.as_ptr() as *mut _;
+            mem::forget(frame);
+
+            match av_interleaved_write_uncoded_frame(self.as_mut_ptr(), stream_index as c_int, ptr)
+            {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
     pub fn write_trailer(&mut self) -> Result<(), Error> {
         unsafe {
             match av_write_trailer(self.as_mut_ptr()) {
@@ -150,6 +281,72 @@ impl Output {
         Ok(chapter)
     }
 
+    /// Add a new program with the given id, or return the existing program
+    /// if one with the same id already exists.
+    ///
+    /// Used by muxers like `mpegts` to write multiple programs (services).
+    pub fn new_program(&mut self, id: i32) -> Result<ProgramMut<'_>, Error> {
+        unsafe {
+            let ptr = av_new_program(self.as_mut_ptr(), id as c_int);
+
+            if ptr.is_null() {
+                return Err(Error::Unknown);
+            }
+
+            let index = (0..self.nb_programs() as usize)
+                .find(|&i| *(*self.as_ptr()).programs.add(i) == ptr)
+                .ok_or(Error::Bug)?;
+
+            Ok(ProgramMut::wrap(&mut self.ctx, index))
+        }
+    }
+
+    /// Add the stream at `stream_index` to the program with the given id.
+    pub fn add_stream_to_program(
+        &mut self,
+        program_id: i32,
+        stream_index: usize,
+    ) -> Result<(), Error> {
+        if stream_index >= self.nb_streams() as usize {
+            return Err(Error::StreamNotFound);
+        }
+
+        if !self.programs().any(|program| program.id() == program_id) {
+            return Err(Error::InvalidData);
+        }
+
+        unsafe {
+            av_program_add_stream_index(
+                self.as_mut_ptr(),
+                program_id as c_int,
+                stream_index as c_uint,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Add a new stream group of the given type.
+    ///
+    /// Streams can then be added with [`StreamGroupMut::add_stream`].
+    #[cfg(feature = "ffmpeg_7_0")]
+    pub fn add_stream_group(
+        &mut self,
+        kind: stream_group::Type,
+    ) -> Result<StreamGroupMut<'_>, Error> {
+        unsafe {
+            let ptr = avformat_stream_group_create(self.as_mut_ptr(), kind.into(), ptr::null_mut());
+
+            if ptr.is_null() {
+                return Err(Error::Unknown);
+            }
+
+            let index = (*ptr).index as usize;
+
+            Ok(StreamGroupMut::wrap(&mut self.ctx, index))
+        }
+    }
+
     pub fn metadata(&self) -> DictionaryRef<'_> {
         unsafe { DictionaryRef::from_raw((*self.as_ptr()).metadata) }
     }
@@ -159,6 +356,22 @@ impl Output {
     }
 }
 
+impl AsPtr<AVFormatContext> for Output {
+    fn as_ptr(&self) -> *const AVFormatContext {
+        self.ptr as *const _
+    }
+}
+
+impl AsMutPtr<AVFormatContext> for Output {
+    fn as_mut_ptr(&mut self) -> *mut AVFormatContext {
+        self.ptr
+    }
+}
+
+impl option::Settable<AVFormatContext> for Output {}
+impl option::Gettable<AVFormatContext> for Output {}
+impl option::Iterable<AVFormatContext> for Output {}
+
 impl Deref for Output {
     type Target = Context;
 
@@ -173,6 +386,69 @@ impl DerefMut for Output {
     }
 }
 
+/// Error returned when a packet could not be written by the muxer.
+///
+/// Contains the underlying [`Error`] and information about the packet which
+/// failed, e.g. to diagnose non-monotonically increasing DTS.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct WriteError {
+    pub error: Error,
+    pub stream: usize,
+    pub pts: Option<i64>,
+    pub dts: Option<i64>,
+    /// Time base of the stream, if the stream exists.
+    pub time_base: Option<Rational>,
+}
+
+impl WriteError {
+    fn new(ctx: &Output, packet: &Packet) -> Self {
+        WriteError {
+            error: Error::Unknown,
+            stream: packet.stream(),
+            pts: packet.pts(),
+            dts: packet.dts(),
+            time_base: ctx.stream(packet.stream()).map(|s| s.time_base()),
+        }
+    }
+
+    fn with(mut self, error: Error) -> Self {
+        self.error = error;
+        self
+    }
+}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "failed to write packet of stream {}", self.stream)?;
+
+        if let Some(dts) = self.dts {
+            write!(f, " at dts {dts}")?;
+        }
+
+        if let Some(pts) = self.pts {
+            write!(f, " (pts {pts})")?;
+        }
+
+        if let Some(time_base) = self.time_base {
+            write!(f, " in time base {time_base}")?;
+        }
+
+        write!(f, ": {}", self.error)
+    }
+}
+
+impl error::Error for WriteError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<WriteError> for Error {
+    fn from(value: WriteError) -> Error {
+        value.error
+    }
+}
+
 pub fn dump(ctx: &Output, index: i32, url: Option<&str>) {
     let url = url.map(|u| CString::new(u).unwrap());
 
@@ -185,3 +461,53 @@ pub fn dump(ctx: &Output, index: i32, url: Option<&str>) {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_error_display() {
+        let error = WriteError {
+            error: Error::InvalidData,
+            stream: 1,
+            pts: Some(3600),
+            dts: Some(1800),
+            time_base: Some(Rational::new(1, 90000)),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "failed to write packet of stream 1 at dts 1800 (pts 3600) in time base 1/90000: {}",
+                Error::InvalidData
+            )
+        );
+
+        let error = WriteError {
+            pts: None,
+            dts: None,
+            time_base: None,
+            ..error
+        };
+
+        assert_eq!(
+            error.to_string(),
+            format!("failed to write packet of stream 1: {}", Error::InvalidData)
+        );
+    }
+    #[test]
+    fn uncoded_frame_stream_bounds() {
+        let mut output = format::output_as("-", "null").unwrap();
+
+        assert!(!output.supports_uncoded_frame(0));
+        assert_eq!(
+            output.write_uncoded_frame(0, crate::frame::Audio::empty()),
+            Err(Error::StreamNotFound)
+        );
+        assert_eq!(
+            output.write_uncoded_frame_interleaved(3, crate::frame::Audio::empty()),
+            Err(Error::StreamNotFound)
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use std::ptr::NonNull;
 
 use crate::ffi::*;
-use crate::utils;
+use crate::{option, utils};
 
 use super::Flags;
 
@@ -27,6 +27,11 @@ impl Input {
         unsafe { utils::optional_str_from_c_ptr((*self.as_ptr()).long_name).unwrap_or("") }
     }
 
+    /// Class of the private options of the format.
+    pub fn priv_class(self) -> Option<option::Class<'static>> {
+        unsafe { option::Class::from_raw((*self.as_ptr()).priv_class) }
+    }
+
     pub fn flags(self) -> Flags {
         unsafe { Flags::from_bits_truncate((*self.as_ptr()).flags) }
     }
@@ -5,7 +5,7 @@ use std::ptr::{self, NonNull};
 
 use super::Flags;
 use crate::ffi::*;
-use crate::{codec, media, utils};
+use crate::{codec, media, option, utils};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Output {
@@ -29,6 +29,11 @@ impl Output {
         unsafe { utils::optional_str_from_c_ptr((*self.as_ptr()).long_name).unwrap_or("") }
     }
 
+    /// Class of the private options of the format.
+    pub fn priv_class(self) -> Option<option::Class<'static>> {
+        unsafe { option::Class::from_raw((*self.as_ptr()).priv_class) }
+    }
+
     pub fn flags(self) -> Flags {
         unsafe { Flags::from_bits_truncate((*self.as_ptr()).flags) }
     }
@@ -9,6 +9,11 @@ pub mod stream;
 
 pub mod chapter;
 
+pub mod program;
+
+#[cfg(feature = "ffmpeg_7_0")]
+pub mod stream_group;
+
 pub mod context;
 pub use self::context::Context;
 
@@ -18,12 +23,16 @@ pub use self::format::{Input, Output};
 
 pub mod network;
 
+pub mod options;
+pub use self::options::{AvoidNegativeTs, InputOptions, OutputOptions};
+
 use std::ffi::{CString, OsStr};
 use std::ptr;
 
 use crate::ffi::*;
 use crate::utils;
 use crate::{AsMutPtr, Error};
+use libc::{c_int, ENOMEM};
 
 pub fn version() -> u32 {
     unsafe { avformat_version() }
@@ -112,6 +121,83 @@ where
     }
 }
 
+/// Open an input with the given [`InputOptions`].
+///
+/// This allows forcing a demuxer, limiting the amount of data used for probing,
+/// passing codec options to `avformat_find_stream_info` or skipping it
+/// altogether.
+pub fn input_with_options<P: AsRef<OsStr>>(
+    path_or_url: P,
+    mut options: InputOptions,
+) -> Result<context::Input, Error> {
+    unsafe {
+        let mut ps = avformat_alloc_context();
+
+        if ps.is_null() {
+            return Err(Error::Other { errno: ENOMEM });
+        }
+
+        let path = from_os_str(path_or_url);
+        let format = options.format.map_or(ptr::null(), |f| f.as_ptr());
+        options.apply(ps);
+
+        match avformat_open_input(
+            &mut ps,
+            path.as_ptr(),
+            format,
+            options.dictionary.as_mut_ptr(),
+        ) {
+            0 if !options.find_stream_info => Ok(context::Input::wrap(ps)),
+
+            0 => {
+                let mut input = context::Input::wrap(ps);
+                input.find_stream_info_with(options.stream_options)?;
+
+                Ok(input)
+            }
+
+            e => Err(Error::from(e)),
+        }
+    }
+}
+
+/// Find a registered demuxer by its short name, e.g. `"mpegts"`.
+pub fn find_input(name: &str) -> Option<Input> {
+    let name = CString::new(name).unwrap();
+
+    unsafe { Input::from_raw(av_find_input_format(name.as_ptr())) }
+}
+
+/// Guess the input format from the first bytes of a file.
+///
+/// `filename_hint` can be used to improve detection, e.g. by
+/// matching against known file extensions.
+///
+/// Returns the most likely demuxer together with its score, which is at most
+/// `AVPROBE_SCORE_MAX` (100). Returns `None` if no demuxer recognizes the data.
+pub fn probe(bytes: &[u8], filename_hint: Option<&str>) -> Option<(Input, i32)> {
+    // the probe buffer has to be followed by AVPROBE_PADDING_SIZE zeroed bytes
+    let mut buf = Vec::with_capacity(bytes.len() + AVPROBE_PADDING_SIZE as usize);
+    buf.extend_from_slice(bytes);
+    buf.resize(bytes.len() + AVPROBE_PADDING_SIZE as usize, 0);
+
+    let filename = CString::new(filename_hint.unwrap_or("")).unwrap();
+
+    unsafe {
+        let data = AVProbeData {
+            filename: filename.as_ptr(),
+            buf: buf.as_mut_ptr(),
+            buf_size: bytes.len() as c_int,
+            mime_type: ptr::null(),
+        };
+
+        let mut score = 0;
+        let format = av_probe_input_format3(&data, 1, &mut score);
+
+        Input::from_raw(format).map(|format| (format, score))
+    }
+}
+
 fn from_os_str(path_or_url: impl AsRef<OsStr>) -> CString {
     CString::new(path_or_url.as_ref().as_encoded_bytes()).unwrap()
 }
@@ -211,3 +297,67 @@ where
 
     Ok(ctx)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Dictionary;
+
+    #[test]
+    fn test_find_input() {
+        assert_eq!(find_input("mpegts").unwrap().name(), "mpegts");
+        assert!(find_input("not-a-demuxer").is_none());
+    }
+
+    #[test]
+    fn test_probe_wav() {
+        let mut header = Vec::new();
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&36u32.to_le_bytes());
+        header.extend_from_slice(b"WAVEfmt ");
+        // the wav demuxer needs more than 32 bytes for probing
+        header.resize(44, 0);
+
+        let (format, score) = probe(&header, None).unwrap();
+        assert_eq!(format.name(), "wav");
+        assert!(score > 0);
+    }
+
+    #[test]
+    fn test_stream_options() {
+        // 16-bit mono PCM at 8 kHz with 100 samples of silence
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&236u32.to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&[1, 0, 1, 0]);
+        wav.extend_from_slice(&8000u32.to_le_bytes());
+        wav.extend_from_slice(&16000u32.to_le_bytes());
+        wav.extend_from_slice(&[2, 0, 16, 0]);
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&200u32.to_le_bytes());
+        wav.resize(244, 0);
+
+        let path = std::env::temp_dir().join(format!("stream-options-{}.wav", std::process::id()));
+        std::fs::write(&path, &wav).unwrap();
+
+        let mut options = Dictionary::new();
+        options.set("not-an-option", "1");
+
+        let input = input_with_options(
+            &path,
+            InputOptions::new().stream_options(vec![options.clone()]),
+        )
+        .unwrap();
+        assert_eq!(input.nb_streams(), 1);
+
+        let mut input =
+            input_with_options(&path, InputOptions::new().find_stream_info(false)).unwrap();
+        let unused = input.find_stream_info_with(vec![options]).unwrap();
+        assert_eq!(unused.len(), 1);
+        assert_eq!(unused[0].get("not-an-option"), Some("1"));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,198 @@
+use crate::ffi::*;
+use crate::format;
+use crate::Dictionary;
+use libc::c_int;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Options used when opening an input with
+/// [`input_with_options`][crate::format::input_with_options].
+///
+/// Every value that is not explicitly set keeps the FFmpeg default.
+#[derive(Clone, Debug)]
+pub struct InputOptions {
+    pub(super) format: Option<format::Input>,
+    pub(super) probesize: Option<i64>,
+    pub(super) analyzeduration: Option<i64>,
+    pub(super) fpsprobesize: Option<i32>,
+    pub(super) find_stream_info: bool,
+    pub(super) stream_options: Vec<Dictionary>,
+    pub(super) dictionary: Dictionary,
+}
+
+impl InputOptions {
+    pub fn new() -> Self {
+        InputOptions {
+            format: None,
+            probesize: None,
+            analyzeduration: None,
+            fpsprobesize: None,
+            find_stream_info: true,
+            stream_options: Vec::new(),
+            dictionary: Dictionary::new(),
+        }
+    }
+
+    /// Force a specific demuxer instead of probing the input.
+    ///
+    /// See [`find_input`][crate::format::find_input] to look up a demuxer by name.
+    pub fn format(mut self, format: format::Input) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Maximum number of bytes read from the input to detect its format.
+    ///
+    /// Must be at least 32.
+    pub fn probesize(mut self, bytes: i64) -> Self {
+        self.probesize = Some(bytes);
+        self
+    }
+
+    /// Maximum duration of the input (in `AV_TIME_BASE` units) that is
+    /// analyzed to detect stream information.
+    pub fn analyzeduration(mut self, duration: i64) -> Self {
+        self.analyzeduration = Some(duration);
+        self
+    }
+
+    /// Number of frames used to detect the frame rate.
+    pub fn fpsprobesize(mut self, frames: i32) -> Self {
+        self.fpsprobesize = Some(frames);
+        self
+    }
+
+    /// Whether `avformat_find_stream_info` is run after opening the input.
+    ///
+    /// Enabled by default. When disabled, stream information can still be read
+    /// later on with [`Input::find_stream_info`][crate::format::context::Input::find_stream_info].
+    pub fn find_stream_info(mut self, value: bool) -> Self {
+        self.find_stream_info = value;
+        self
+    }
+
+    /// Codec options for each stream, passed to `avformat_find_stream_info`.
+    ///
+    /// The `n`-th dictionary applies to the `n`-th stream, see
+    /// [`Input::find_stream_info_with`][crate::format::context::Input::find_stream_info_with].
+    /// Options that are not consumed are discarded; to inspect them, disable
+    /// [`find_stream_info`][InputOptions::find_stream_info] and call
+    /// `find_stream_info_with` on the opened input instead.
+    pub fn stream_options(mut self, options: Vec<Dictionary>) -> Self {
+        self.stream_options = options;
+        self
+    }
+
+    /// Demuxer and protocol private options, passed to `avformat_open_input`.
+    pub fn dictionary(mut self, dictionary: Dictionary) -> Self {
+        self.dictionary = dictionary;
+        self
+    }
+
+    pub(super) unsafe fn apply(&self, ctx: *mut AVFormatContext) {
+        if let Some(probesize) = self.probesize {
+            (*ctx).probesize = probesize;
+        }
+
+        if let Some(analyzeduration) = self.analyzeduration {
+            (*ctx).max_analyze_duration = analyzeduration;
+        }
+
+        if let Some(fpsprobesize) = self.fpsprobesize {
+            (*ctx).fps_probe_size = fpsprobesize as c_int;
+        }
+    }
+}
+
+impl Default for InputOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How the muxer shifts timestamps to avoid negative values.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum AvoidNegativeTs {
+    /// Shift timestamps only if the output format requires it.
+    Auto,
+    /// Never shift timestamps.
+    Disabled,
+    /// Shift timestamps so that they are non-negative.
+    MakeNonNegative,
+    /// Shift timestamps so that the first timestamp is 0.
+    MakeZero,
+}
+
+impl From<AvoidNegativeTs> for c_int {
+    fn from(value: AvoidNegativeTs) -> c_int {
+        match value {
+            AvoidNegativeTs::Auto => AVFMT_AVOID_NEG_TS_AUTO,
+            AvoidNegativeTs::Disabled => AVFMT_AVOID_NEG_TS_DISABLED,
+            AvoidNegativeTs::MakeNonNegative => AVFMT_AVOID_NEG_TS_MAKE_NON_NEGATIVE,
+            AvoidNegativeTs::MakeZero => AVFMT_AVOID_NEG_TS_MAKE_ZERO,
+        }
+    }
+}
+
+/// Muxer options which control interleaving and timestamp handling.
+///
+/// Applied with [`Output::set_options`][crate::format::context::Output::set_options]
+/// before writing the header. Every value that is not explicitly set keeps the
+/// FFmpeg default.
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Default)]
+pub struct OutputOptions {
+    max_interleave_delta: Option<i64>,
+    avoid_negative_ts: Option<AvoidNegativeTs>,
+    output_ts_offset: Option<i64>,
+    flush_packets: Option<bool>,
+}
+
+impl OutputOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum buffering duration (in microseconds) for interleaving.
+    ///
+    /// If set to 0, packets are buffered until there is one for every stream.
+    pub fn max_interleave_delta(mut self, delta: i64) -> Self {
+        self.max_interleave_delta = Some(delta);
+        self
+    }
+
+    pub fn avoid_negative_ts(mut self, value: AvoidNegativeTs) -> Self {
+        self.avoid_negative_ts = Some(value);
+        self
+    }
+
+    /// Offset (in `AV_TIME_BASE` units) added to all output timestamps.
+    pub fn output_ts_offset(mut self, offset: i64) -> Self {
+        self.output_ts_offset = Some(offset);
+        self
+    }
+
+    /// Whether the I/O context is flushed after every packet.
+    pub fn flush_packets(mut self, value: bool) -> Self {
+        self.flush_packets = Some(value);
+        self
+    }
+
+    pub(crate) unsafe fn apply(&self, ctx: *mut AVFormatContext) {
+        if let Some(delta) = self.max_interleave_delta {
+            (*ctx).max_interleave_delta = delta;
+        }
+
+        if let Some(value) = self.avoid_negative_ts {
+            (*ctx).avoid_negative_ts = value.into();
+        }
+
+        if let Some(offset) = self.output_ts_offset {
+            (*ctx).output_ts_offset = offset;
+        }
+
+        if let Some(value) = self.flush_packets {
+            (*ctx).flush_packets = c_int::from(value);
+        }
+    }
+}
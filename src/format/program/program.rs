@@ -0,0 +1,82 @@
+use std::slice;
+
+use crate::ffi::*;
+use crate::format::context::common::Context;
+use crate::{DictionaryRef, Discard};
+
+// WARNING: index refers to the offset in the programs array (starting from 0)
+// it is not necessarily equal to the id
+pub struct Program<'a> {
+    context: &'a Context,
+    index: usize,
+}
+
+impl<'a> Program<'a> {
+    pub unsafe fn wrap(context: &Context, index: usize) -> Program<'_> {
+        Program { context, index }
+    }
+
+    pub unsafe fn as_ptr(&self) -> *const AVProgram {
+        *(*self.context.as_ptr()).programs.add(self.index)
+    }
+}
+
+impl<'a> Program<'a> {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn id(&self) -> i32 {
+        unsafe { (*self.as_ptr()).id }
+    }
+
+    /// The MPEG-TS program number (service id).
+    pub fn program_num(&self) -> i32 {
+        unsafe { (*self.as_ptr()).program_num }
+    }
+
+    /// The PID carrying the program map table, or -1 if unknown.
+    pub fn pmt_pid(&self) -> i32 {
+        unsafe { (*self.as_ptr()).pmt_pid }
+    }
+
+    /// The PID carrying the program clock reference, or -1 if unknown.
+    pub fn pcr_pid(&self) -> i32 {
+        unsafe { (*self.as_ptr()).pcr_pid }
+    }
+
+    pub fn pmt_version(&self) -> i32 {
+        unsafe { (*self.as_ptr()).pmt_version }
+    }
+
+    pub fn discard(&self) -> Discard {
+        unsafe { Discard::from((*self.as_ptr()).discard) }
+    }
+
+    /// Indices of the streams which belong to this program.
+    pub fn stream_indices(&self) -> Vec<usize> {
+        unsafe {
+            let ptr = (*self.as_ptr()).stream_index;
+            let len = (*self.as_ptr()).nb_stream_indexes as usize;
+
+            if ptr.is_null() {
+                Vec::new()
+            } else {
+                slice::from_raw_parts(ptr, len)
+                    .iter()
+                    .map(|&index| index as usize)
+                    .collect()
+            }
+        }
+    }
+
+    pub fn metadata(&self) -> DictionaryRef<'_> {
+        unsafe { DictionaryRef::from_raw((*self.as_ptr()).metadata) }
+    }
+}
+
+impl<'a> PartialEq for Program<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { self.as_ptr() == other.as_ptr() }
+    }
+}
@@ -0,0 +1,80 @@
+use std::mem;
+use std::ops::Deref;
+
+use super::Program;
+use crate::ffi::*;
+use crate::format::context::common::Context;
+use crate::{DictionaryMut, Discard};
+use libc::c_uint;
+
+// WARNING: index refers to the offset in the programs array (starting from 0)
+// it is not necessarily equal to the id
+pub struct ProgramMut<'a> {
+    context: &'a mut Context,
+    index: usize,
+
+    immutable: Program<'a>,
+}
+
+impl<'a> ProgramMut<'a> {
+    pub unsafe fn wrap(context: &mut Context, index: usize) -> ProgramMut<'_> {
+        ProgramMut {
+            context: mem::transmute_copy(&context),
+            index,
+
+            immutable: Program::wrap(mem::transmute_copy(&context), index),
+        }
+    }
+
+    pub unsafe fn as_mut_ptr(&mut self) -> *mut AVProgram {
+        *(*self.context.as_mut_ptr()).programs.add(self.index)
+    }
+}
+
+impl<'a> ProgramMut<'a> {
+    pub fn set_program_num(&mut self, value: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).program_num = value;
+        }
+    }
+
+    pub fn set_pmt_pid(&mut self, value: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).pmt_pid = value;
+        }
+    }
+
+    pub fn set_pcr_pid(&mut self, value: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).pcr_pid = value;
+        }
+    }
+
+    pub fn set_discard(&mut self, value: Discard) {
+        unsafe {
+            (*self.as_mut_ptr()).discard = value.into();
+        }
+    }
+
+    /// Add the stream at `stream_index` to this program.
+    ///
+    /// Does nothing if the stream is already part of the program.
+    pub fn add_stream(&mut self, stream_index: usize) {
+        unsafe {
+            let id = (*self.as_mut_ptr()).id;
+            av_program_add_stream_index(self.context.as_mut_ptr(), id, stream_index as c_uint);
+        }
+    }
+
+    pub fn metadata_mut(&mut self) -> DictionaryMut<'_> {
+        unsafe { DictionaryMut::from_raw(&mut (*self.as_mut_ptr()).metadata) }
+    }
+}
+
+impl<'a> Deref for ProgramMut<'a> {
+    type Target = Program<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.immutable
+    }
+}
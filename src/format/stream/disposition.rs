@@ -18,5 +18,11 @@ bitflags::bitflags! {
         const CAPTIONS         = AV_DISPOSITION_CAPTIONS;
         const DESCRIPTIONS     = AV_DISPOSITION_DESCRIPTIONS;
         const METADATA         = AV_DISPOSITION_METADATA;
+        const TIMED_THUMBNAILS = AV_DISPOSITION_TIMED_THUMBNAILS;
+        const NON_DIEGETIC     = AV_DISPOSITION_NON_DIEGETIC;
+        const DEPENDENT        = AV_DISPOSITION_DEPENDENT;
+        const STILL_IMAGE      = AV_DISPOSITION_STILL_IMAGE;
+        #[cfg(feature = "ffmpeg_7_1")]
+        const MULTILAYER       = AV_DISPOSITION_MULTILAYER;
     }
 }
@@ -2,12 +2,11 @@ use super::Disposition;
 use crate::codec;
 use crate::ffi::*;
 use crate::format::context::common::Context;
-use crate::{DictionaryRef, Discard, Rational};
+use crate::{media, DictionaryRef, Discard, Rational, Timestamp};
+use libc::c_int;
 
 #[cfg(not(feature = "ffmpeg_8_0"))]
 use crate::codec::packet;
-#[cfg(not(feature = "ffmpeg_8_0"))]
-use libc::c_int;
 
 #[derive(Debug)]
 pub struct Stream<'a> {
@@ -48,6 +47,14 @@ impl<'a> Stream<'a> {
         unsafe { (*self.as_ptr()).start_time }
     }
 
+    /// The start time in the time base of the stream, if known.
+    pub fn start_timestamp(&self) -> Option<Timestamp> {
+        match self.start_time() {
+            AV_NOPTS_VALUE => None,
+            start => Some(Timestamp::new(start, self.time_base())),
+        }
+    }
+
     pub fn duration(&self) -> i64 {
         unsafe { (*self.as_ptr()).duration }
     }
@@ -69,6 +76,38 @@ impl<'a> Stream<'a> {
         SideDataIter::new(self)
     }
 
+    /// Whether enough codec parameters are known to decode this stream.
+    ///
+    /// This mirrors the check done by `avformat_find_stream_info`, which does not
+    /// fail if only some of the streams could not be analyzed. A stream for which
+    /// this returns `false` after probing usually needs a larger `probesize` or
+    /// `analyzeduration`.
+    ///
+    /// Note that `codec_info_nb_frames` is internal to libavformat since FFmpeg 5.0
+    /// and cannot be exposed.
+    pub fn has_codec_parameters(&self) -> bool {
+        let parameters = self.parameters();
+        let format = unsafe { (*parameters.as_ptr()).format };
+
+        let complete = match parameters.medium() {
+            media::Type::Audio => {
+                parameters.sample_rate() != 0
+                    && parameters.ch_layout().channels() != 0
+                    && format != AVSampleFormat::NONE.0 as c_int
+            }
+            media::Type::Video => {
+                parameters.width() != 0 && format != AVPixelFormat::NONE.0 as c_int
+            }
+            media::Type::Subtitle if parameters.id() == codec::Id::HDMV_PGS_SUBTITLE => {
+                parameters.width() != 0
+            }
+            media::Type::Data if parameters.id() == codec::Id::None => return true,
+            _ => true,
+        };
+
+        complete && parameters.id() != codec::Id::None
+    }
+
     pub fn rate(&self) -> Rational {
         unsafe { Rational::from((*self.as_ptr()).r_frame_rate) }
     }
@@ -81,6 +120,16 @@ impl<'a> Stream<'a> {
         unsafe { DictionaryRef::from_raw((*self.as_ptr()).metadata) }
     }
 
+    /// The `language` metadata entry, usually an ISO 639-2 code like `eng`.
+    pub fn language(&self) -> Option<&str> {
+        self.metadata().get("language")
+    }
+
+    /// The `title` metadata entry.
+    pub fn title(&self) -> Option<&str> {
+        self.metadata().get("title")
+    }
+
     pub fn sample_aspect_ratio(&self) -> Rational {
         unsafe { Rational::from((*self.as_ptr()).sample_aspect_ratio) }
     }
@@ -1,11 +1,12 @@
 use std::mem;
 use std::ops::Deref;
 
-use super::Stream;
+use super::{Disposition, Stream};
 use crate::ffi::*;
 use crate::format::context::common::Context;
 use crate::AsPtr;
-use crate::{codec, DictionaryMut, Rational};
+use crate::{codec, DictionaryMut, Discard, Error, Rational};
+use libc::{c_uint, EINVAL};
 
 pub struct StreamMut<'a> {
     context: &'a mut Context,
@@ -30,6 +31,13 @@ impl<'a> StreamMut<'a> {
 }
 
 impl<'a> StreamMut<'a> {
+    /// Set the format-specific stream id, e.g. the PID for MPEG-TS.
+    pub fn set_id(&mut self, value: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).id = value;
+        }
+    }
+
     pub fn set_time_base<R: Into<Rational>>(&mut self, value: R) {
         unsafe {
             (*self.as_mut_ptr()).time_base = value.into().into();
@@ -48,6 +56,62 @@ impl<'a> StreamMut<'a> {
         }
     }
 
+    /// Set the start time in [`time_base`][Stream::time_base] units.
+    pub fn set_start_time(&mut self, value: i64) {
+        unsafe {
+            (*self.as_mut_ptr()).start_time = value;
+        }
+    }
+
+    /// Set the duration in [`time_base`][Stream::time_base] units.
+    pub fn set_duration(&mut self, value: i64) {
+        unsafe {
+            (*self.as_mut_ptr()).duration = value;
+        }
+    }
+
+    pub fn set_disposition(&mut self, value: Disposition) {
+        unsafe {
+            (*self.as_mut_ptr()).disposition = value.bits();
+        }
+    }
+
+    pub fn set_discard(&mut self, value: Discard) {
+        unsafe {
+            (*self.as_mut_ptr()).discard = value.into();
+        }
+    }
+
+    /// Set the codec tag (FourCC) written by the muxer, e.g.
+    /// `u32::from_le_bytes(*b"hvc1")`.
+    ///
+    /// A value of 0 lets the muxer choose the tag.
+    pub fn set_codec_tag(&mut self, value: u32) {
+        unsafe {
+            (*(*self.as_mut_ptr()).codecpar).codec_tag = value as c_uint;
+        }
+    }
+
+    /// Set the `language` metadata entry.
+    ///
+    /// `language` must be an ISO 639-2 code of three ASCII letters (e.g. `eng`),
+    /// which is stored in lowercase. Use `und` for an undetermined language.
+    pub fn set_language(&mut self, language: &str) -> Result<(), Error> {
+        if language.len() != 3 || !language.bytes().all(|b| b.is_ascii_alphabetic()) {
+            return Err(Error::Other { errno: EINVAL });
+        }
+
+        self.metadata_mut()
+            .set("language", language.to_ascii_lowercase());
+
+        Ok(())
+    }
+
+    /// Set the `title` metadata entry.
+    pub fn set_title(&mut self, title: &str) {
+        self.metadata_mut().set("title", title);
+    }
+
     pub fn parameters_mut(&mut self) -> codec::ParametersMut<'_> {
         unsafe {
             codec::ParametersMut::from_raw((*self.as_mut_ptr()).codecpar)
@@ -0,0 +1,14 @@
+//! Stream groups (`AVStreamGroup`), available since FFmpeg 7.0.
+//!
+//! Stream groups describe relations between streams which go beyond
+//! programs, e.g. IAMF audio elements and mix presentations or the tiles
+//! of a HEIF image grid.
+
+pub mod params;
+pub use self::params::{Tile, TileGrid, Type};
+
+mod stream_group;
+pub use self::stream_group::StreamGroup;
+
+mod stream_group_mut;
+pub use self::stream_group_mut::StreamGroupMut;
@@ -0,0 +1,136 @@
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+use crate::ffi::*;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Type {
+    None,
+    IamfAudioElement,
+    IamfMixPresentation,
+    TileGrid,
+    #[cfg(feature = "ffmpeg_7_1")]
+    Lcevc,
+}
+
+impl From<AVStreamGroupParamsType> for Type {
+    fn from(value: AVStreamGroupParamsType) -> Self {
+        use AVStreamGroupParamsType as AV;
+
+        match value {
+            AV::NONE => Type::None,
+            AV::IAMF_AUDIO_ELEMENT => Type::IamfAudioElement,
+            AV::IAMF_MIX_PRESENTATION => Type::IamfMixPresentation,
+            AV::TILE_GRID => Type::TileGrid,
+            #[cfg(feature = "ffmpeg_7_1")]
+            AV::LCEVC => Type::Lcevc,
+
+            _ => unimplemented!(),
+        }
+    }
+}
+
+impl From<Type> for AVStreamGroupParamsType {
+    fn from(value: Type) -> AVStreamGroupParamsType {
+        use AVStreamGroupParamsType as AV;
+
+        match value {
+            Type::None => AV::NONE,
+            Type::IamfAudioElement => AV::IAMF_AUDIO_ELEMENT,
+            Type::IamfMixPresentation => AV::IAMF_MIX_PRESENTATION,
+            Type::TileGrid => AV::TILE_GRID,
+            #[cfg(feature = "ffmpeg_7_1")]
+            Type::Lcevc => AV::LCEVC,
+        }
+    }
+}
+
+/// Placement of a single tile in a [`TileGrid`].
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Tile {
+    /// Index of the tile stream within the stream group (not the format context).
+    pub stream: usize,
+    /// Offset in pixels from the left edge of the canvas.
+    pub horizontal: i32,
+    /// Offset in pixels from the top edge of the canvas.
+    pub vertical: i32,
+}
+
+/// Parameters of a [`Type::TileGrid`] stream group.
+pub struct TileGrid<'a> {
+    ptr: NonNull<AVStreamGroupTileGrid>,
+    _marker: PhantomData<&'a AVStreamGroupTileGrid>,
+}
+
+impl<'a> TileGrid<'a> {
+    pub unsafe fn from_raw(ptr: *const AVStreamGroupTileGrid) -> Option<Self> {
+        NonNull::new(ptr as *mut _).map(|ptr| Self {
+            ptr,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn as_ptr(&self) -> *const AVStreamGroupTileGrid {
+        self.ptr.as_ptr()
+    }
+}
+
+impl<'a> TileGrid<'a> {
+    /// Width of the canvas the tiles are placed on.
+    pub fn coded_width(&self) -> u32 {
+        unsafe { (*self.as_ptr()).coded_width as u32 }
+    }
+
+    /// Height of the canvas the tiles are placed on.
+    pub fn coded_height(&self) -> u32 {
+        unsafe { (*self.as_ptr()).coded_height as u32 }
+    }
+
+    pub fn tiles(&self) -> Vec<Tile> {
+        unsafe {
+            let offsets = (*self.as_ptr()).offsets;
+            let nb_tiles = (*self.as_ptr()).nb_tiles as usize;
+
+            (0..nb_tiles)
+                .map(|i| {
+                    let offset = &*offsets.add(i);
+
+                    Tile {
+                        stream: offset.idx as usize,
+                        horizontal: offset.horizontal,
+                        vertical: offset.vertical,
+                    }
+                })
+                .collect()
+        }
+    }
+
+    /// Background color of the canvas, as RGBA.
+    pub fn background(&self) -> [u8; 4] {
+        unsafe { (*self.as_ptr()).background }
+    }
+
+    /// Horizontal offset of the output image within the canvas.
+    pub fn horizontal_offset(&self) -> i32 {
+        unsafe { (*self.as_ptr()).horizontal_offset }
+    }
+
+    /// Vertical offset of the output image within the canvas.
+    pub fn vertical_offset(&self) -> i32 {
+        unsafe { (*self.as_ptr()).vertical_offset }
+    }
+
+    /// Width of the output image.
+    pub fn width(&self) -> u32 {
+        unsafe { (*self.as_ptr()).width as u32 }
+    }
+
+    /// Height of the output image.
+    pub fn height(&self) -> u32 {
+        unsafe { (*self.as_ptr()).height as u32 }
+    }
+}
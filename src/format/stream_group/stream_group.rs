@@ -0,0 +1,87 @@
+use super::{TileGrid, Type};
+use crate::ffi::*;
+use crate::format::context::common::Context;
+use crate::format::stream::Disposition;
+use crate::DictionaryRef;
+
+pub struct StreamGroup<'a> {
+    context: &'a Context,
+    index: usize,
+}
+
+impl<'a> StreamGroup<'a> {
+    pub unsafe fn wrap(context: &Context, index: usize) -> StreamGroup<'_> {
+        StreamGroup { context, index }
+    }
+
+    pub unsafe fn as_ptr(&self) -> *const AVStreamGroup {
+        *(*self.context.as_ptr()).stream_groups.add(self.index)
+    }
+}
+
+impl<'a> StreamGroup<'a> {
+    pub fn index(&self) -> usize {
+        unsafe { (*self.as_ptr()).index as usize }
+    }
+
+    pub fn id(&self) -> i64 {
+        unsafe { (*self.as_ptr()).id }
+    }
+
+    pub fn kind(&self) -> Type {
+        unsafe { Type::from((*self.as_ptr()).type_) }
+    }
+
+    pub fn disposition(&self) -> Disposition {
+        unsafe { Disposition::from_bits_truncate((*self.as_ptr()).disposition) }
+    }
+
+    pub fn metadata(&self) -> DictionaryRef<'_> {
+        unsafe { DictionaryRef::from_raw((*self.as_ptr()).metadata) }
+    }
+
+    /// Indices (in the format context) of the streams in this group.
+    pub fn stream_indices(&self) -> Vec<usize> {
+        unsafe {
+            let streams = (*self.as_ptr()).streams;
+            let nb_streams = (*self.as_ptr()).nb_streams as usize;
+
+            (0..nb_streams)
+                .map(|i| (**streams.add(i)).index as usize)
+                .collect()
+        }
+    }
+
+    /// Tile grid parameters, if this is a [`Type::TileGrid`] group.
+    pub fn tile_grid(&self) -> Option<TileGrid<'_>> {
+        if self.kind() != Type::TileGrid {
+            return None;
+        }
+
+        unsafe { TileGrid::from_raw((*self.as_ptr()).params.tile_grid) }
+    }
+
+    /// Raw pointer to the `AVIAMFAudioElement` of a [`Type::IamfAudioElement`] group.
+    pub unsafe fn iamf_audio_element(&self) -> Option<*const AVIAMFAudioElement> {
+        if self.kind() != Type::IamfAudioElement {
+            return None;
+        }
+
+        Some((*self.as_ptr()).params.iamf_audio_element as *const _)
+    }
+
+    /// Raw pointer to the `AVIAMFMixPresentation` of a [`Type::IamfMixPresentation`] group.
+    pub unsafe fn iamf_mix_presentation(&self) -> Option<*const AVIAMFMixPresentation> {
+        if self.kind() != Type::IamfMixPresentation {
+            return None;
+        }
+
+        Some((*self.as_ptr()).params.iamf_mix_presentation as *const _)
+    }
+}
+
+impl<'a> PartialEq for StreamGroup<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { self.as_ptr() == other.as_ptr() }
+    }
+}
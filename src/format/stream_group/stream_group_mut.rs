@@ -0,0 +1,72 @@
+use std::mem;
+use std::ops::Deref;
+
+use super::StreamGroup;
+use crate::ffi::*;
+use crate::format::context::common::Context;
+use crate::format::stream::Disposition;
+use crate::{DictionaryMut, Error};
+
+pub struct StreamGroupMut<'a> {
+    context: &'a mut Context,
+    index: usize,
+
+    immutable: StreamGroup<'a>,
+}
+
+impl<'a> StreamGroupMut<'a> {
+    pub unsafe fn wrap(context: &mut Context, index: usize) -> StreamGroupMut<'_> {
+        StreamGroupMut {
+            context: mem::transmute_copy(&context),
+            index,
+
+            immutable: StreamGroup::wrap(mem::transmute_copy(&context), index),
+        }
+    }
+
+    pub unsafe fn as_mut_ptr(&mut self) -> *mut AVStreamGroup {
+        *(*self.context.as_mut_ptr()).stream_groups.add(self.index)
+    }
+}
+
+impl<'a> StreamGroupMut<'a> {
+    pub fn set_id(&mut self, value: i64) {
+        unsafe {
+            (*self.as_mut_ptr()).id = value;
+        }
+    }
+
+    pub fn set_disposition(&mut self, value: Disposition) {
+        unsafe {
+            (*self.as_mut_ptr()).disposition = value.bits();
+        }
+    }
+
+    /// Add the stream at `stream_index` (in the format context) to this group.
+    pub fn add_stream(&mut self, stream_index: usize) -> Result<(), Error> {
+        if stream_index >= self.context.nb_streams() as usize {
+            return Err(Error::StreamNotFound);
+        }
+
+        unsafe {
+            let stream = *(*self.context.as_mut_ptr()).streams.add(stream_index);
+
+            match avformat_stream_group_add_stream(self.as_mut_ptr(), stream) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    pub fn metadata_mut(&mut self) -> DictionaryMut<'_> {
+        unsafe { DictionaryMut::from_raw(&mut (*self.as_mut_ptr()).metadata) }
+    }
+}
+
+impl<'a> Deref for StreamGroupMut<'a> {
+    type Target = StreamGroup<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.immutable
+    }
+}
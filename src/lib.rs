@@ -6,12 +6,14 @@ pub use crate::util::channel_layout::{
     self, Channel, ChannelCustom, ChannelLayout, ChannelLayoutIter, ChannelLayoutMask, ChannelOrder,
 };
 pub use crate::util::{
+    audio_fifo::{self, AudioFifo},
     chroma, color, dictionary,
     dictionary::{Dictionary, DictionaryMut, DictionaryRef},
     error::{self, Error},
     frame::{self, Frame},
-    log,
-    mathematics::{self, rescale, Rescale, Rounding},
+    hwcontext, log,
+    loudness::{self, LoudnessMeter},
+    mathematics::{self, rescale, Rescale, Rounding, Timestamp},
     media, option, picture,
     rational::{self, Rational},
     time,
@@ -25,6 +27,7 @@ pub mod format;
 #[cfg(feature = "format")]
 pub use crate::format::{
     chapter::{Chapter, ChapterMut},
+    program::{Program, ProgramMut},
     stream::{Stream, StreamMut},
 };
 
@@ -0,0 +1,176 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+
+use crate::Error;
+
+/// A context which can be created from a key and reused by a [`Cache`].
+pub trait Cacheable: Sized + Send {
+    type Key: Eq + Clone + Send;
+
+    fn create(key: &Self::Key) -> Result<Self, Error>;
+
+    /// Prepares a context taken out of the cache for a new user, undoing
+    /// changes the previous user may have made to the configuration in `key`.
+    fn reset(&mut self, _key: &Self::Key) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// A thread-safe pool of idle contexts, keyed by their configuration.
+///
+/// Contexts are handed out exclusively by [`get`][Cache::get] and go back to
+/// the cache when the returned [`Cached`] is dropped. When more than
+/// `capacity` contexts are idle, the least recently used ones are freed.
+pub struct Cache<C: Cacheable> {
+    capacity: usize,
+    idle: Mutex<VecDeque<(C::Key, C)>>,
+}
+
+impl<C: Cacheable> Cache<C> {
+    pub fn new(capacity: usize) -> Self {
+        Cache {
+            capacity,
+            idle: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of idle contexts.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Frees all idle contexts.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Takes an idle context for `key` out of the cache, or creates a new one.
+    pub fn get(&self, key: &C::Key) -> Result<Cached<'_, C>, Error> {
+        let idle = {
+            let mut idle = self.lock();
+            idle.iter()
+                .position(|(k, _)| k == key)
+                .and_then(|index| idle.remove(index))
+        };
+
+        let context = match idle {
+            Some((_, mut context)) => {
+                context.reset(key)?;
+                context
+            }
+
+            None => C::create(key)?,
+        };
+
+        Ok(Cached {
+            cache: self,
+            key: key.clone(),
+            context: Some(context),
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<(C::Key, C)>> {
+        // A panic while holding the lock cannot leave the queue inconsistent.
+        self.idle.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn release(&self, key: C::Key, context: C) {
+        let mut idle = self.lock();
+        idle.push_front((key, context));
+        idle.truncate(self.capacity);
+    }
+}
+
+impl<C: Cacheable> fmt::Debug for Cache<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Cache")
+            .field("capacity", &self.capacity)
+            .field("idle", &self.len())
+            .finish()
+    }
+}
+
+/// A context borrowed from a [`Cache`], returned to it when dropped.
+pub struct Cached<'a, C: Cacheable> {
+    cache: &'a Cache<C>,
+    key: C::Key,
+    context: Option<C>,
+}
+
+impl<'a, C: Cacheable> Cached<'a, C> {
+    pub fn key(&self) -> &C::Key {
+        &self.key
+    }
+
+    /// Takes the context out, it will not return to the cache.
+    pub fn detach(mut self) -> C {
+        self.context.take().expect("context is present")
+    }
+}
+
+impl<'a, C: Cacheable> Deref for Cached<'a, C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        self.context.as_ref().expect("context is present")
+    }
+}
+
+impl<'a, C: Cacheable> DerefMut for Cached<'a, C> {
+    fn deref_mut(&mut self) -> &mut C {
+        self.context.as_mut().expect("context is present")
+    }
+}
+
+impl<'a, C: Cacheable> Drop for Cached<'a, C> {
+    fn drop(&mut self) {
+        if let Some(context) = self.context.take() {
+            self.cache.release(self.key.clone(), context);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Dummy(u32);
+
+    impl Cacheable for Dummy {
+        type Key = u32;
+
+        fn create(key: &u32) -> Result<Self, Error> {
+            Ok(Dummy(*key))
+        }
+    }
+
+    #[test]
+    fn reuse_and_evict() {
+        let cache = Cache::<Dummy>::new(2);
+
+        {
+            let a = cache.get(&1).unwrap();
+            let b = cache.get(&1).unwrap();
+            let _c = cache.get(&2).unwrap();
+            assert_eq!(a.0 + b.0, 2);
+        }
+
+        // Released in reverse order: 2, then both 1s; only two are kept.
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&1).unwrap().0, 1);
+
+        let detached = cache.get(&3).unwrap().detach();
+        assert_eq!(detached.0, 3);
+        assert_eq!(cache.len(), 2);
+    }
+}
@@ -1,3 +1,6 @@
+#[cfg(any(feature = "software-scaling", feature = "software-resampling"))]
+pub mod cache;
+
 #[cfg(feature = "software-scaling")]
 pub mod scaling;
 
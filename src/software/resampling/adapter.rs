@@ -0,0 +1,230 @@
+use std::ptr;
+
+use super::Context;
+use crate::codec::encoder;
+use crate::ffi::*;
+use crate::util::format;
+use crate::{frame, AudioFifo, ChannelLayout, Error, Packet, Rational};
+use libc::EAGAIN;
+
+/// Feeds an audio encoder with frames of arbitrary format and size.
+///
+/// Input frames are converted to the sample format, channel layout and rate of
+/// the encoder, buffered in an [`AudioFifo`] and sent to the encoder in frames
+/// of [`frame_size`][encoder::audio::Encoder::frame_size] samples, with
+/// continuous timestamps in the encoder time base.
+pub struct EncoderAdapter {
+    resampler: Context,
+    fifo: AudioFifo,
+    frame_size: usize,
+    time_base: Rational,
+}
+
+impl EncoderAdapter {
+    /// Creates an adapter for input frames in the given format, whose
+    /// timestamps are in `time_base`. `encoder` must already be opened.
+    pub fn new(
+        format: format::Sample,
+        layout: &ChannelLayout,
+        rate: u32,
+        time_base: Rational,
+        encoder: &encoder::audio::Encoder,
+    ) -> Result<Self, Error> {
+        let resampler = Context::get2(
+            format,
+            layout.clone(),
+            rate,
+            encoder.format(),
+            encoder.ch_layout(),
+            encoder.rate(),
+        )?;
+
+        let mut fifo = AudioFifo::new(encoder.format(), &encoder.ch_layout(), encoder.rate())?;
+        fifo.set_time_base(unsafe { Rational::from((*encoder.as_ptr()).time_base) });
+
+        Ok(EncoderAdapter {
+            resampler,
+            fifo,
+            frame_size: encoder.frame_size() as usize,
+            time_base,
+        })
+    }
+
+    pub fn fifo(&self) -> &AudioFifo {
+        &self.fifo
+    }
+
+    /// Converts and buffers `frame`, then sends all complete frames to
+    /// `encoder`. Every packet produced by the encoder is passed to `packet`.
+    pub fn send_frame<F>(
+        &mut self,
+        frame: &frame::Audio,
+        encoder: &mut encoder::audio::Encoder,
+        packet: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(Packet) -> Result<(), Error>,
+    {
+        self.convert(unsafe { frame.as_ptr() }, frame.pts())?;
+        self.encode(encoder, false, packet)
+    }
+
+    /// Flushes the resampler and the FIFO, whose last frame may be shorter
+    /// than the encoder frame size, and signals the end of stream to
+    /// `encoder`. All remaining packets are passed to `packet`.
+    pub fn send_eof<F>(
+        &mut self,
+        encoder: &mut encoder::audio::Encoder,
+        packet: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(Packet) -> Result<(), Error>,
+    {
+        self.convert(ptr::null(), None)?;
+        self.encode(encoder, true, packet)
+    }
+
+    // Converts `input`, whose pts is in the input time base, and buffers the
+    // result.
+    fn convert(&mut self, input: *const AVFrame, pts: Option<i64>) -> Result<(), Error> {
+        let fifo = self.fifo.time_base();
+        let pts = pts.map(|pts| self.resampler.output_pts(Some(pts), self.time_base, fifo));
+
+        let mut output = frame::Audio::empty();
+        output.set_format(self.fifo.format());
+        output.set_rate(self.fifo.rate());
+
+        unsafe {
+            let ret = av_channel_layout_copy(
+                &mut (*output.as_mut_ptr()).ch_layout,
+                self.fifo.ch_layout().as_ptr(),
+            );
+            if ret < 0 {
+                return Err(Error::from(ret));
+            }
+
+            // The output frame is allocated by swresample, large enough for
+            // all buffered samples.
+            let ret = swr_convert_frame(self.resampler.as_mut_ptr(), output.as_mut_ptr(), input);
+            if ret < 0 {
+                return Err(Error::from(ret));
+            }
+        }
+
+        if output.samples() == 0 {
+            return Ok(());
+        }
+
+        output.set_pts(pts);
+        self.fifo.write(&output)
+    }
+
+    fn encode<F>(
+        &mut self,
+        encoder: &mut encoder::audio::Encoder,
+        eof: bool,
+        mut packet: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(Packet) -> Result<(), Error>,
+    {
+        loop {
+            let frame = match self.frame_size {
+                0 => self.fifo.read_remaining()?,
+                size => match self.fifo.read(size)? {
+                    None if eof => self.fifo.read_remaining()?,
+                    frame => frame,
+                },
+            };
+
+            let Some(frame) = frame else {
+                break;
+            };
+
+            encoder.send_frame(&frame)?;
+            receive(encoder, &mut packet)?;
+        }
+
+        if eof {
+            encoder.send_eof()?;
+            receive(encoder, &mut packet)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn receive<F>(encoder: &mut encoder::audio::Encoder, packet: &mut F) -> Result<(), Error>
+where
+    F: FnMut(Packet) -> Result<(), Error>,
+{
+    loop {
+        let mut out = Packet::empty();
+
+        match encoder.receive_packet(&mut out) {
+            Ok(()) => packet(out)?,
+            Err(Error::Eof) | Err(Error::Other { errno: EAGAIN }) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::{self, Context as CodecContext};
+    use crate::ChannelLayoutMask;
+
+    #[test]
+    fn continuous_pts() {
+        let format = format::Sample::I16(format::sample::Type::Packed);
+        let codec = encoder::find(codec::Id::PCM_S16LE).unwrap();
+
+        let mut encoder = CodecContext::new_with_codec(codec)
+            .encoder()
+            .audio()
+            .unwrap();
+        encoder.set_rate(48000);
+        encoder.set_format(format);
+        encoder.set_ch_layout(ChannelLayout::MONO);
+        encoder.set_time_base(Rational::new(1, 48000));
+        let mut encoder = encoder.open_as(codec).unwrap();
+
+        let time_base = Rational::new(1, 44100);
+        let mut adapter =
+            EncoderAdapter::new(format, &ChannelLayout::MONO, 44100, time_base, &encoder).unwrap();
+
+        let mut packets = Vec::new();
+
+        for index in 0..4 {
+            let mut frame = frame::Audio::new(format, 1000, ChannelLayoutMask::MONO);
+            frame.set_rate(44100);
+            frame.set_pts(Some(index * 1000));
+            frame.data_mut(0).fill(0);
+
+            adapter
+                .send_frame(&frame, &mut encoder, |packet| {
+                    packets.push(packet);
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        adapter
+            .send_eof(&mut encoder, |packet| {
+                packets.push(packet);
+                Ok(())
+            })
+            .unwrap();
+
+        // 4000 samples at 44.1 kHz are about 4354 samples at 48 kHz.
+        let mut samples = 0;
+
+        for packet in &packets {
+            assert_eq!(packet.pts(), Some(samples));
+            samples += packet.size() as i64 / 2;
+        }
+
+        assert!((4350..=4360).contains(&samples));
+    }
+}
@@ -0,0 +1,49 @@
+use super::{Context, Definition};
+use crate::ffi::*;
+use crate::software::cache::{Cache, Cacheable};
+use crate::{ChannelLayout, Error};
+
+/// Configuration of a cached resampling [`Context`].
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+pub struct Key {
+    pub input: Definition,
+    pub output: Definition,
+}
+
+impl Key {
+    pub fn new(input: Definition, output: Definition) -> Self {
+        Key { input, output }
+    }
+}
+
+impl Cacheable for Context {
+    type Key = Key;
+
+    fn create(key: &Key) -> Result<Self, Error> {
+        let layout = |definition: &Definition| {
+            ChannelLayout::from_mask(definition.channel_layout).ok_or(Error::InvalidData)
+        };
+
+        Context::get2(
+            key.input.format,
+            layout(&key.input)?,
+            key.input.rate,
+            key.output.format,
+            layout(&key.output)?,
+            key.output.rate,
+        )
+    }
+
+    /// Drops buffered samples of the previous user.
+    fn reset(&mut self, _key: &Key) -> Result<(), Error> {
+        unsafe {
+            match swr_init(self.as_mut_ptr()) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+}
+
+/// A thread-safe cache of resampling contexts, see [`Cache`].
+pub type ResamplerCache = Cache<Context>;
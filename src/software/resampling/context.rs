@@ -1,17 +1,17 @@
 use std::ptr;
 
-use super::Delay;
+use super::{Delay, Matrix};
 use crate::ffi::*;
 use crate::util::format;
-use crate::AsMutPtr;
 use crate::Dictionary;
-use crate::{frame, ChannelLayoutMask, Error};
-use libc::c_int;
+use crate::{frame, ChannelLayoutMask, Error, Rational};
+use crate::{option, AsMutPtr, AsPtr};
+use libc::{c_int, EINVAL};
 use std::ffi::c_void;
 
 use crate::ChannelLayout;
 
-#[derive(Eq, PartialEq, Copy, Clone)]
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
 pub struct Definition {
     pub format: format::Sample,
     pub channel_layout: ChannelLayoutMask,
@@ -23,6 +23,9 @@ pub struct Context {
 
     input: Definition,
     output: Definition,
+
+    // swresample keeps a pointer to the channel mapping.
+    channel_map: Option<Vec<c_int>>,
 }
 
 unsafe impl Send for Context {}
@@ -112,6 +115,8 @@ impl Context {
                             channel_layout: dst_channel_layout,
                             rate: dst_rate,
                         },
+
+                        channel_map: None,
                     }),
                 }
             } else {
@@ -195,6 +200,8 @@ impl Context {
                             channel_layout: dst_channel_layout.mask().unwrap(),
                             rate: dst_rate,
                         },
+
+                        channel_map: None,
                     }),
                 }
             } else {
@@ -268,8 +275,202 @@ impl Context {
             }
         }
     }
+
+    /// Number of output samples produced for `in_samples` more input samples,
+    /// including the buffered ones. This is an upper bound, suitable to size
+    /// output frames.
+    pub fn get_out_samples(&mut self, in_samples: usize) -> Result<usize, Error> {
+        unsafe {
+            match swr_get_out_samples(self.as_mut_ptr(), in_samples as c_int) {
+                e if e < 0 => Err(Error::from(e)),
+                samples => Ok(samples as usize),
+            }
+        }
+    }
+
+    /// Convert the next input timestamp to an output timestamp.
+    ///
+    /// Both are expressed in units of `1 / (input rate * output rate)`
+    /// seconds. Passing `i64::MIN` returns the timestamp of the next output
+    /// sample without changing the compensation state.
+    pub fn next_pts(&mut self, pts: i64) -> i64 {
+        unsafe { swr_next_pts(self.as_mut_ptr(), pts) }
+    }
+
+    /// Computes the pts of the next output samples from the `pts` of the next
+    /// input frame, accounting for the samples buffered by the resampler.
+    ///
+    /// `pts` is in `time_base` and the result in `output_time_base`. Passing
+    /// `None` continues from the previous output, as when flushing. This has
+    /// to be called before converting the input frame, see af_aresample.
+    pub(crate) fn output_pts(
+        &mut self,
+        pts: Option<i64>,
+        time_base: Rational,
+        output_time_base: Rational,
+    ) -> i64 {
+        let rates = i64::from(self.input.rate) * i64::from(self.output.rate);
+
+        unsafe {
+            let pts = pts.map_or(i64::MIN, |pts| {
+                av_rescale(
+                    pts,
+                    rates * i64::from(time_base.numerator()),
+                    i64::from(time_base.denominator()),
+                )
+            });
+
+            av_rescale(
+                self.next_pts(pts),
+                i64::from(output_time_base.denominator()),
+                rates * i64::from(output_time_base.numerator()),
+            )
+        }
+    }
+
+    /// Activate resampling compensation ("soft" drift correction), adding
+    /// `sample_delta` samples over the next `compensation_distance` output
+    /// samples.
+    pub fn set_compensation(
+        &mut self,
+        sample_delta: i32,
+        compensation_distance: i32,
+    ) -> Result<(), Error> {
+        unsafe {
+            match swr_set_compensation(
+                self.as_mut_ptr(),
+                sample_delta as c_int,
+                compensation_distance as c_int,
+            ) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Set a custom input channel mapping: `map[i]` is the index of the input
+    /// channel used as channel `i`, or `-1` for a muted channel.
+    ///
+    /// The context is reinitialized, dropping any buffered samples.
+    pub fn set_channel_mapping(&mut self, map: &[i32]) -> Result<(), Error> {
+        let channels = self.input.channel_layout.channels();
+
+        if map.len() != channels as usize || map.iter().any(|&i| i < -1 || i >= channels) {
+            return Err(Error::Other { errno: EINVAL });
+        }
+
+        // Store the mapping first, swresample keeps pointing to it even if
+        // the reinitialization fails.
+        let map = self
+            .channel_map
+            .insert(map.iter().map(|&i| i as c_int).collect())
+            .as_ptr();
+
+        unsafe { self.reinit(|ptr| swr_set_channel_mapping(ptr, map)) }
+    }
+
+    /// Set a custom rematrixing matrix, see [`Matrix::build`] for the
+    /// default one.
+    ///
+    /// The context is reinitialized, dropping any buffered samples.
+    pub fn set_matrix(&mut self, matrix: &Matrix) -> Result<(), Error> {
+        if matrix.inputs() != self.input.channel_layout.channels() as usize
+            || matrix.outputs() != self.output.channel_layout.channels() as usize
+        {
+            return Err(Error::Other { errno: EINVAL });
+        }
+
+        unsafe {
+            self.reinit(|ptr| {
+                swr_set_matrix(
+                    ptr,
+                    matrix.coefficients().as_ptr(),
+                    matrix.inputs() as c_int,
+                )
+            })
+        }
+    }
+
+    unsafe fn reinit<F>(&mut self, configure: F) -> Result<(), Error>
+    where
+        F: FnOnce(*mut SwrContext) -> c_int,
+    {
+        swr_close(self.ptr);
+
+        let configured = configure(self.ptr);
+        let initialized = swr_init(self.ptr);
+
+        if configured < 0 {
+            Err(Error::from(configured))
+        } else if initialized < 0 {
+            Err(Error::from(initialized))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Run the resampler on `input`, or flush it if `input` is `None`,
+    /// computing the output pts from the input pts.
+    ///
+    /// Timestamps of both frames are in `time_base`. If `output` is empty it
+    /// is allocated with room for all the available samples.
+    ///
+    /// When there are internal frames to process it will return `Ok(Some(Delay { .. }))`.
+    pub fn run_frame(
+        &mut self,
+        input: Option<&frame::Audio>,
+        output: &mut frame::Audio,
+        time_base: Rational,
+    ) -> Result<Option<Delay>, Error> {
+        if output.is_empty() {
+            output.set_format(self.output.format);
+            output.set_rate(self.output.rate);
+
+            if let Some(layout) = ChannelLayout::from_mask(self.output.channel_layout) {
+                output.set_ch_layout(layout);
+            }
+        }
+
+        let pts = match input {
+            Some(frame) => frame
+                .pts()
+                .map(|pts| self.output_pts(Some(pts), time_base, time_base)),
+            None => Some(self.output_pts(None, time_base, time_base)),
+        };
+
+        unsafe {
+            (*output.as_mut_ptr()).sample_rate = self.output.rate as i32;
+
+            let input_ptr = input.map_or(ptr::null(), |frame| frame.as_ptr());
+
+            match swr_convert_frame(self.as_mut_ptr(), output.as_mut_ptr(), input_ptr) {
+                0 => (),
+                e => return Err(Error::from(e)),
+            }
+        }
+
+        output.set_pts(pts);
+
+        Ok(self.delay())
+    }
+}
+
+impl AsPtr<SwrContext> for Context {
+    fn as_ptr(&self) -> *const SwrContext {
+        self.ptr as *const _
+    }
+}
+
+impl AsMutPtr<SwrContext> for Context {
+    fn as_mut_ptr(&mut self) -> *mut SwrContext {
+        self.ptr
+    }
 }
 
+impl option::Settable<SwrContext> for Context {}
+impl option::Gettable<SwrContext> for Context {}
+impl option::Iterable<SwrContext> for Context {}
+
 impl Drop for Context {
     fn drop(&mut self) {
         unsafe {
@@ -277,3 +478,51 @@ impl Drop for Context {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_frame_pts() {
+        let format = format::Sample::I16(format::sample::Type::Packed);
+        let time_base = Rational::new(1, 48000);
+
+        let mut context = Context::get2(
+            format,
+            ChannelLayout::MONO,
+            48000,
+            format,
+            ChannelLayout::MONO,
+            44100,
+        )
+        .unwrap();
+
+        // The pts of every output frame is the time of its first sample.
+        let mut produced = 0;
+        let expected = |produced: usize| (produced * 48000) as f64 / 44100.0;
+
+        for index in 0..4 {
+            let mut input = frame::Audio::new(format, 1024, ChannelLayoutMask::MONO);
+            input.set_rate(48000);
+            input.set_pts(Some(index * 1024));
+            input.data_mut(0).fill(0);
+
+            let mut output = frame::Audio::empty();
+            context
+                .run_frame(Some(&input), &mut output, time_base)
+                .unwrap();
+
+            let pts = output.pts().unwrap() as f64;
+            assert!((pts - expected(produced)).abs() <= 1.0);
+            produced += output.samples();
+        }
+
+        let mut output = frame::Audio::empty();
+        context.run_frame(None, &mut output, time_base).unwrap();
+
+        let pts = output.pts().unwrap() as f64;
+        assert!((pts - expected(produced)).abs() <= 1.0);
+        assert!(output.samples() > 0);
+    }
+}
@@ -0,0 +1,298 @@
+use std::f64::consts::FRAC_1_SQRT_2;
+use std::ptr;
+
+use crate::ffi::*;
+use crate::{ChannelLayout, ChannelOrder, Error};
+use libc::{c_int, EINVAL};
+
+/// Stereo matrix encodings which can be applied when downmixing.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum MatrixEncoding {
+    None,
+    Dolby,
+    DolbyProLogicII,
+    DolbyProLogicIIx,
+    DolbyProLogicIIz,
+    DolbyEx,
+    DolbyHeadphone,
+}
+
+impl From<AVMatrixEncoding> for MatrixEncoding {
+    fn from(value: AVMatrixEncoding) -> MatrixEncoding {
+        match value {
+            AVMatrixEncoding::_NONE => MatrixEncoding::None,
+            AVMatrixEncoding::_DOLBY => MatrixEncoding::Dolby,
+            AVMatrixEncoding::_DPLII => MatrixEncoding::DolbyProLogicII,
+            AVMatrixEncoding::_DPLIIX => MatrixEncoding::DolbyProLogicIIx,
+            AVMatrixEncoding::_DPLIIZ => MatrixEncoding::DolbyProLogicIIz,
+            AVMatrixEncoding::_DOLBYEX => MatrixEncoding::DolbyEx,
+            AVMatrixEncoding::_DOLBYHEADPHONE => MatrixEncoding::DolbyHeadphone,
+
+            _ => MatrixEncoding::None,
+        }
+    }
+}
+
+impl From<MatrixEncoding> for AVMatrixEncoding {
+    fn from(value: MatrixEncoding) -> AVMatrixEncoding {
+        match value {
+            MatrixEncoding::None => AVMatrixEncoding::_NONE,
+            MatrixEncoding::Dolby => AVMatrixEncoding::_DOLBY,
+            MatrixEncoding::DolbyProLogicII => AVMatrixEncoding::_DPLII,
+            MatrixEncoding::DolbyProLogicIIx => AVMatrixEncoding::_DPLIIX,
+            MatrixEncoding::DolbyProLogicIIz => AVMatrixEncoding::_DPLIIZ,
+            MatrixEncoding::DolbyEx => AVMatrixEncoding::_DOLBYEX,
+            MatrixEncoding::DolbyHeadphone => AVMatrixEncoding::_DOLBYHEADPHONE,
+        }
+    }
+}
+
+/// Options of [`Matrix::build_with_options`] and
+/// [`ChannelLayout::mix_matrix_to`].
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct MixOptions {
+    /// Gain of the center channel when mixed into other channels.
+    pub center_mix_level: f64,
+    /// Gain of the surround channels when mixed into other channels.
+    pub surround_mix_level: f64,
+    /// Gain of the LFE channel when mixed into other channels.
+    pub lfe_mix_level: f64,
+    /// Scales the coefficients so that no output channel can clip.
+    pub normalize: bool,
+    /// Gain applied to all coefficients, after normalization.
+    pub volume: f64,
+    /// Only used for stereo outputs.
+    pub encoding: MatrixEncoding,
+}
+
+impl Default for MixOptions {
+    /// The options of [`Matrix::build`].
+    fn default() -> Self {
+        MixOptions {
+            center_mix_level: FRAC_1_SQRT_2,
+            surround_mix_level: FRAC_1_SQRT_2,
+            lfe_mix_level: 0.0,
+            normalize: true,
+            volume: 1.0,
+            encoding: MatrixEncoding::None,
+        }
+    }
+}
+
+/// A rematrixing matrix, with one row of coefficients per output channel and
+/// one column per input channel.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Matrix {
+    inputs: usize,
+    outputs: usize,
+    coefficients: Vec<f64>,
+}
+
+impl Matrix {
+    /// Creates a matrix with all coefficients set to 0.
+    pub fn new(inputs: usize, outputs: usize) -> Self {
+        Matrix {
+            inputs,
+            outputs,
+            coefficients: vec![0.0; inputs * outputs],
+        }
+    }
+
+    /// Creates a matrix from row-major coefficients, `coefficients[o * inputs + i]`
+    /// being the gain of input channel `i` in output channel `o`.
+    pub fn from_coefficients(
+        inputs: usize,
+        outputs: usize,
+        coefficients: Vec<f64>,
+    ) -> Result<Self, Error> {
+        if coefficients.len() != inputs * outputs {
+            return Err(Error::Other { errno: EINVAL });
+        }
+
+        Ok(Matrix {
+            inputs,
+            outputs,
+            coefficients,
+        })
+    }
+
+    /// Builds the matrix FFmpeg would use to mix `input` into `output`.
+    ///
+    /// Center and surround channels are mixed at -3dB and the LFE channel is
+    /// dropped, the coefficients are normalized to avoid clipping.
+    pub fn build(input: &ChannelLayout, output: &ChannelLayout) -> Result<Self, Error> {
+        Self::build_with_options(input, output, MixOptions::default())
+    }
+
+    /// Builds the matrix to mix `input` into `output`, with custom levels
+    /// (as linear gains) for the center, surround and LFE channels.
+    pub fn build_with(
+        input: &ChannelLayout,
+        output: &ChannelLayout,
+        center_mix_level: f64,
+        surround_mix_level: f64,
+        lfe_mix_level: f64,
+    ) -> Result<Self, Error> {
+        Self::build_with_options(
+            input,
+            output,
+            MixOptions {
+                center_mix_level,
+                surround_mix_level,
+                lfe_mix_level,
+                ..MixOptions::default()
+            },
+        )
+    }
+
+    /// Builds the matrix to mix `input` into `output` with `options`.
+    ///
+    /// Ambisonic inputs are not supported by FFmpeg, as a placeholder for a
+    /// proper (e.g. binaural) decoder their omnidirectional W channel is
+    /// copied to every output channel.
+    pub fn build_with_options(
+        input: &ChannelLayout,
+        output: &ChannelLayout,
+        options: MixOptions,
+    ) -> Result<Self, Error> {
+        let mut matrix = Matrix::new(input.channels() as usize, output.channels() as usize);
+
+        if input.order() == ChannelOrder::Ambisonic && output.order() != ChannelOrder::Ambisonic {
+            if matrix.inputs == 0 {
+                return Err(Error::Other { errno: EINVAL });
+            }
+
+            for row in 0..matrix.outputs {
+                matrix.set(row, 0, options.volume);
+            }
+
+            return Ok(matrix);
+        }
+
+        let maxval = if options.normalize {
+            1.0
+        } else {
+            f64::from(c_int::MAX)
+        };
+
+        unsafe {
+            let ret = swr_build_matrix2(
+                input.as_ptr(),
+                output.as_ptr(),
+                options.center_mix_level,
+                options.surround_mix_level,
+                options.lfe_mix_level,
+                maxval,
+                // Only negative volumes are handled by FFmpeg when building
+                // the matrix, as a custom normalization value.
+                1.0,
+                matrix.coefficients.as_mut_ptr(),
+                matrix.inputs as isize,
+                options.encoding.into(),
+                ptr::null_mut(),
+            );
+
+            if ret < 0 {
+                return Err(Error::from(ret));
+            }
+        }
+
+        for coefficient in &mut matrix.coefficients {
+            *coefficient *= options.volume;
+        }
+
+        Ok(matrix)
+    }
+
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    pub fn outputs(&self) -> usize {
+        self.outputs
+    }
+
+    /// Gain of input channel `input` in output channel `output`.
+    pub fn get(&self, output: usize, input: usize) -> f64 {
+        assert!(input < self.inputs && output < self.outputs);
+        self.coefficients[output * self.inputs + input]
+    }
+
+    pub fn set(&mut self, output: usize, input: usize, value: f64) {
+        assert!(input < self.inputs && output < self.outputs);
+        self.coefficients[output * self.inputs + input] = value;
+    }
+
+    /// Coefficients of output channel `output`, one per input channel.
+    pub fn row(&self, output: usize) -> &[f64] {
+        &self.coefficients[output * self.inputs..(output + 1) * self.inputs]
+    }
+
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coefficients
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coefficients() {
+        assert!(Matrix::from_coefficients(2, 1, vec![0.5]).is_err());
+
+        let mut matrix = Matrix::from_coefficients(2, 1, vec![0.5, 0.5]).unwrap();
+        matrix.set(0, 1, 0.25);
+
+        assert_eq!(matrix.get(0, 0), 0.5);
+        assert_eq!(matrix.row(0), &[0.5, 0.25]);
+    }
+
+    #[test]
+    fn downmix() {
+        let options = MixOptions {
+            lfe_mix_level: 0.5,
+            normalize: false,
+            ..MixOptions::default()
+        };
+
+        // FL, FR, FC, LFE, SL, SR into FL, FR
+        let matrix =
+            Matrix::build_with_options(&ChannelLayout::_5POINT1, &ChannelLayout::STEREO, options)
+                .unwrap();
+        assert_eq!((matrix.inputs(), matrix.outputs()), (6, 2));
+
+        let c = FRAC_1_SQRT_2;
+        let lfe = 0.5 * FRAC_1_SQRT_2;
+        let expected = [[1.0, 0.0, c, lfe, c, 0.0], [0.0, 1.0, c, lfe, 0.0, c]];
+
+        for (row, expected) in expected.iter().enumerate() {
+            for (actual, expected) in matrix.row(row).iter().zip(expected) {
+                assert!((actual - expected).abs() < 1e-9, "{:?}", matrix.row(row));
+            }
+        }
+
+        let options = MixOptions {
+            volume: 0.5,
+            ..options
+        };
+        let halved =
+            Matrix::build_with_options(&ChannelLayout::_5POINT1, &ChannelLayout::STEREO, options)
+                .unwrap();
+        assert_eq!(halved.get(0, 0), 0.5);
+    }
+
+    #[test]
+    fn encoding() {
+        for encoding in [
+            MatrixEncoding::None,
+            MatrixEncoding::DolbyProLogicII,
+            MatrixEncoding::DolbyHeadphone,
+        ] {
+            assert_eq!(
+                MatrixEncoding::from(AVMatrixEncoding::from(encoding)),
+                encoding
+            );
+        }
+    }
+}
@@ -13,8 +13,19 @@ pub use self::filter::Filter;
 pub mod delay;
 pub use self::delay::Delay;
 
+pub mod matrix;
+pub use self::matrix::{Matrix, MatrixEncoding, MixOptions};
+
 pub mod context;
-pub use self::context::Context;
+pub use self::context::{Context, Definition};
+
+pub mod cache;
+pub use self::cache::ResamplerCache;
+
+#[cfg(feature = "codec")]
+pub mod adapter;
+#[cfg(feature = "codec")]
+pub use self::adapter::EncoderAdapter;
 
 mod extensions;
 
@@ -0,0 +1,95 @@
+use super::{ColorspaceDetails, Context, Definition, Flags};
+use crate::software::cache::{Cache, Cacheable};
+use crate::Error;
+
+/// Configuration of a cached scaling [`Context`].
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+pub struct Key {
+    pub input: Definition,
+    pub output: Definition,
+    pub flags: Flags,
+    /// Applied with [`Context::set_details`] if set.
+    pub details: Option<ColorspaceDetails>,
+}
+
+impl Key {
+    pub fn new(input: Definition, output: Definition, flags: Flags) -> Self {
+        Key {
+            input,
+            output,
+            flags,
+            details: None,
+        }
+    }
+
+    pub fn details(mut self, details: ColorspaceDetails) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+impl Cacheable for Context {
+    type Key = Key;
+
+    fn create(key: &Key) -> Result<Self, Error> {
+        let mut context = Context::get(
+            key.input.format,
+            key.input.width,
+            key.input.height,
+            key.output.format,
+            key.output.width,
+            key.output.height,
+            key.flags,
+        )?;
+
+        if let Some(details) = &key.details {
+            context.set_details(details)?;
+        }
+
+        Ok(context)
+    }
+
+    /// Restores the colorspace details of `key`, or the defaults if it has none.
+    fn reset(&mut self, key: &Key) -> Result<(), Error> {
+        self.set_details(&key.details.unwrap_or_default())
+    }
+}
+
+/// A thread-safe cache of scaling contexts, see [`Cache`].
+pub type ScalerCache = Cache<Context>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::Pixel;
+
+    #[test]
+    fn reset_details() {
+        let cache = ScalerCache::new(1);
+        let key = Key::new(
+            Definition {
+                format: Pixel::YUV420P,
+                width: 16,
+                height: 16,
+            },
+            Definition {
+                format: Pixel::RGB24,
+                width: 16,
+                height: 16,
+            },
+            Flags::BILINEAR,
+        );
+
+        {
+            let mut context = cache.get(&key).unwrap();
+            let details = ColorspaceDetails {
+                brightness: 1 << 14,
+                ..ColorspaceDetails::default()
+            };
+            context.set_details(&details).unwrap();
+        }
+
+        let context = cache.get(&key).unwrap();
+        assert_eq!(context.colorspace_details().unwrap().brightness, 0);
+    }
+}
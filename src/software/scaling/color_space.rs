@@ -1,9 +1,10 @@
+use crate::color;
 use crate::ffi::*;
 use libc::c_int;
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum ColorSpace {
     Default,
@@ -14,6 +15,7 @@ pub enum ColorSpace {
     ITU624,
     SMPTE170M,
     SMPTE240M,
+    BT2020,
 }
 
 impl From<c_int> for ColorSpace {
@@ -23,6 +25,7 @@ impl From<c_int> for ColorSpace {
             SWS_CS_FCC => ColorSpace::FCC,
             SWS_CS_DEFAULT => ColorSpace::Default,
             SWS_CS_SMPTE240M => ColorSpace::SMPTE240M,
+            SWS_CS_BT2020 => ColorSpace::BT2020,
 
             _ => ColorSpace::Default,
         }
@@ -39,6 +42,91 @@ impl From<ColorSpace> for c_int {
             ColorSpace::ITU624 => SWS_CS_ITU624,
             ColorSpace::SMPTE170M => SWS_CS_SMPTE170M,
             ColorSpace::SMPTE240M => SWS_CS_SMPTE240M,
+            ColorSpace::BT2020 => SWS_CS_BT2020,
+        }
+    }
+}
+
+/// Maps the color space of a frame to the matching YUV coefficients.
+impl From<color::Space> for ColorSpace {
+    fn from(value: color::Space) -> ColorSpace {
+        match value {
+            color::Space::BT709 => ColorSpace::ITU709,
+            color::Space::FCC => ColorSpace::FCC,
+            color::Space::BT470BG => ColorSpace::ITU601,
+            color::Space::SMPTE170M => ColorSpace::SMPTE170M,
+            color::Space::SMPTE240M => ColorSpace::SMPTE240M,
+            color::Space::BT2020NCL | color::Space::BT2020CL => ColorSpace::BT2020,
+
+            _ => ColorSpace::Default,
+        }
+    }
+}
+
+impl From<ColorSpace> for color::Space {
+    fn from(value: ColorSpace) -> color::Space {
+        match value {
+            ColorSpace::ITU709 => color::Space::BT709,
+            ColorSpace::FCC => color::Space::FCC,
+            ColorSpace::ITU601 | ColorSpace::ITU624 | ColorSpace::Default => color::Space::BT470BG,
+            ColorSpace::SMPTE170M => color::Space::SMPTE170M,
+            ColorSpace::SMPTE240M => color::Space::SMPTE240M,
+            ColorSpace::BT2020 => color::Space::BT2020NCL,
+        }
+    }
+}
+
+impl ColorSpace {
+    /// The YUV to RGB coefficients used by swscale for this color space.
+    pub(crate) fn coefficients(self) -> *const c_int {
+        unsafe { sws_getCoefficients(self.into()) }
+    }
+
+    /// Finds the color space whose coefficients are `table`.
+    pub(crate) unsafe fn from_coefficients(table: *const c_int) -> ColorSpace {
+        const ALL: [ColorSpace; 5] = [
+            ColorSpace::ITU709,
+            ColorSpace::FCC,
+            ColorSpace::ITU601,
+            ColorSpace::SMPTE240M,
+            ColorSpace::BT2020,
+        ];
+
+        let table = std::slice::from_raw_parts(table, 4);
+
+        ALL.into_iter()
+            .find(|space| std::slice::from_raw_parts(space.coefficients(), 4) == table)
+            .unwrap_or(ColorSpace::Default)
+    }
+}
+
+/// YUV <-> RGB conversion settings of a scaling context, see
+/// [`Context::set_colorspace_details`][super::Context::set_colorspace_details].
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ColorspaceDetails {
+    pub src_space: ColorSpace,
+    pub src_range: color::Range,
+    pub dst_space: ColorSpace,
+    pub dst_range: color::Range,
+    /// 16.16 fixed point, 0 by default.
+    pub brightness: i32,
+    /// 16.16 fixed point, `1 << 16` by default.
+    pub contrast: i32,
+    /// 16.16 fixed point, `1 << 16` by default.
+    pub saturation: i32,
+}
+
+impl Default for ColorspaceDetails {
+    fn default() -> Self {
+        ColorspaceDetails {
+            src_space: ColorSpace::Default,
+            src_range: color::Range::MPEG,
+            dst_space: ColorSpace::Default,
+            dst_range: color::Range::MPEG,
+            brightness: 0,
+            contrast: 1 << 16,
+            saturation: 1 << 16,
         }
     }
 }
@@ -1,12 +1,12 @@
 use std::ptr;
 
-use super::Flags;
+use super::{ColorSpace, ColorspaceDetails, Flags};
 use crate::ffi::*;
 use crate::util::format;
-use crate::{frame, Error};
+use crate::{color, frame, option, AsMutPtr, AsPtr, Error};
 use libc::c_int;
 
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
 pub struct Definition {
     pub format: format::Pixel,
     pub width: u32,
@@ -18,8 +18,14 @@ pub struct Context {
 
     input: Definition,
     output: Definition,
+    flags: Flags,
+
+    // Colorspace details to restore at the end of a slice based conversion.
+    frame_details: Option<ColorspaceDetails>,
 }
 
+unsafe impl Send for Context {}
+
 impl Context {
     #[inline(always)]
     pub unsafe fn as_ptr(&self) -> *const SwsContext {
@@ -71,6 +77,10 @@ impl Context {
                         width: dst_w,
                         height: dst_h,
                     },
+
+                    flags,
+
+                    frame_details: None,
                 })
             } else {
                 Err(Error::InvalidData)
@@ -78,6 +88,13 @@ impl Context {
         }
     }
 
+    /// Reconfigures the context for new parameters.
+    ///
+    /// Like `sws_getCachedContext`, nothing is done if the parameters did not
+    /// change. Otherwise a new context is created with [`get`][Self::get],
+    /// which resets previously set
+    /// [colorspace details][Self::set_colorspace_details]. On failure the
+    /// context is left unchanged.
     #[allow(clippy::too_many_arguments)]
     pub fn cached(
         &mut self,
@@ -88,34 +105,29 @@ impl Context {
         dst_w: u32,
         dst_h: u32,
         flags: Flags,
-    ) {
-        self.input = Definition {
+    ) -> Result<(), Error> {
+        let input = Definition {
             format: src_format,
             width: src_w,
             height: src_h,
         };
 
-        self.output = Definition {
+        let output = Definition {
             format: dst_format,
             width: dst_w,
             height: dst_h,
         };
 
-        unsafe {
-            self.ptr = sws_getCachedContext(
-                self.as_mut_ptr(),
-                src_w as c_int,
-                src_h as c_int,
-                src_format.into(),
-                dst_w as c_int,
-                dst_h as c_int,
-                dst_format.into(),
-                flags.bits(),
-                ptr::null_mut(),
-                ptr::null_mut(),
-                ptr::null(),
-            );
+        if input == self.input && output == self.output && flags == self.flags {
+            return Ok(());
         }
+
+        // sws_getCachedContext frees the passed context when it fails, create
+        // the new one separately to keep `self` valid.
+        let context = Context::get(src_format, src_w, src_h, dst_format, dst_w, dst_h, flags)?;
+        *self = context;
+
+        Ok(())
     }
 
     #[inline]
@@ -128,6 +140,11 @@ impl Context {
         &self.output
     }
 
+    #[inline]
+    pub fn flags(&self) -> Flags {
+        self.flags
+    }
+
     pub fn run(&mut self, input: &frame::Video, output: &mut frame::Video) -> Result<(), Error> {
         if input.format() != self.input.format
             || input.width() != self.input.width
@@ -165,6 +182,292 @@ impl Context {
     }
 }
 
+impl Context {
+    /// Configures the YUV <-> RGB conversion.
+    ///
+    /// `brightness`, `contrast` and `saturation` are 16.16 fixed point values,
+    /// with defaults of 0, `1 << 16` and `1 << 16`. The color space of an RGB
+    /// side is ignored.
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_colorspace_details(
+        &mut self,
+        src_space: ColorSpace,
+        src_range: color::Range,
+        dst_space: ColorSpace,
+        dst_range: color::Range,
+        brightness: i32,
+        contrast: i32,
+        saturation: i32,
+    ) -> Result<(), Error> {
+        self.set_details(&ColorspaceDetails {
+            src_space,
+            src_range,
+            dst_space,
+            dst_range,
+            brightness,
+            contrast,
+            saturation,
+        })
+    }
+
+    pub fn set_details(&mut self, details: &ColorspaceDetails) -> Result<(), Error> {
+        unsafe {
+            match sws_setColorspaceDetails(
+                self.as_mut_ptr(),
+                details.src_space.coefficients(),
+                c_int::from(details.src_range == color::Range::JPEG),
+                details.dst_space.coefficients(),
+                c_int::from(details.dst_range == color::Range::JPEG),
+                details.brightness,
+                details.contrast,
+                details.saturation,
+            ) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// The current YUV <-> RGB conversion settings.
+    ///
+    /// Returns `None` if the context does not convert between YUV and RGB.
+    pub fn colorspace_details(&self) -> Option<ColorspaceDetails> {
+        unsafe {
+            let mut inv_table = ptr::null_mut();
+            let mut src_range = 0;
+            let mut table = ptr::null_mut();
+            let mut dst_range = 0;
+            let mut brightness = 0;
+            let mut contrast = 0;
+            let mut saturation = 0;
+
+            let ret = sws_getColorspaceDetails(
+                self.ptr,
+                &mut inv_table,
+                &mut src_range,
+                &mut table,
+                &mut dst_range,
+                &mut brightness,
+                &mut contrast,
+                &mut saturation,
+            );
+
+            if ret < 0 {
+                return None;
+            }
+
+            let range = |value: c_int| {
+                if value != 0 {
+                    color::Range::JPEG
+                } else {
+                    color::Range::MPEG
+                }
+            };
+
+            Some(ColorspaceDetails {
+                src_space: ColorSpace::from_coefficients(inv_table),
+                src_range: range(src_range),
+                dst_space: ColorSpace::from_coefficients(table),
+                dst_range: range(dst_range),
+                brightness,
+                contrast,
+                saturation,
+            })
+        }
+    }
+
+    /// Scales `input` into `output` with `sws_scale_frame`.
+    ///
+    /// Unlike [`run`][Self::run], the YUV <-> RGB conversion follows the color
+    /// space and range of `input`. The color range of `output` is used as
+    /// destination range if it is specified, and the resulting color metadata
+    /// and timestamp are set on `output`.
+    ///
+    /// The [colorspace details][Self::set_details] of the context are only
+    /// changed for this conversion and restored afterwards.
+    pub fn run_frame(
+        &mut self,
+        input: &frame::Video,
+        output: &mut frame::Video,
+    ) -> Result<(), Error> {
+        let previous = self.prepare_frame(input, output)?;
+
+        let ret =
+            unsafe { sws_scale_frame(self.as_mut_ptr(), output.as_mut_ptr(), input.as_ptr()) };
+
+        if let Some(details) = previous {
+            self.set_details(&details)?;
+        }
+
+        match ret {
+            e if e < 0 => Err(Error::from(e)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Starts a slice based conversion of `input` into `output`, with the same
+    /// color handling as [`run_frame`][Self::run_frame].
+    ///
+    /// Input rows are made available with [`send_slice`][Self::send_slice]
+    /// and output rows are produced with [`receive_slice`][Self::receive_slice].
+    /// Call [`frame_end`][Self::frame_end] once the frame is done, which also
+    /// restores the colorspace details of the context.
+    pub fn frame_start(
+        &mut self,
+        input: &frame::Video,
+        output: &mut frame::Video,
+    ) -> Result<(), Error> {
+        let previous = self.prepare_frame(input, output)?;
+
+        unsafe {
+            match sws_frame_start(self.as_mut_ptr(), output.as_mut_ptr(), input.as_ptr()) {
+                e if e < 0 => {
+                    if let Some(details) = previous {
+                        self.set_details(&details)?;
+                    }
+
+                    Err(Error::from(e))
+                }
+                _ => {
+                    self.frame_details = previous;
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Marks the input rows `start..start + height` as available.
+    pub fn send_slice(&mut self, start: u32, height: u32) -> Result<(), Error> {
+        unsafe {
+            match sws_send_slice(self.as_mut_ptr(), start, height) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Produces the output rows `start..start + height`.
+    ///
+    /// Fails with [`Error::Other`] (`EAGAIN`) if more input is needed.
+    pub fn receive_slice(&mut self, start: u32, height: u32) -> Result<(), Error> {
+        unsafe {
+            match sws_receive_slice(self.as_mut_ptr(), start, height) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Alignment required for the `start` and `height` of output slices, except
+    /// for the last slice of a frame.
+    pub fn receive_slice_alignment(&self) -> u32 {
+        unsafe { sws_receive_slice_alignment(self.ptr) }
+    }
+
+    pub fn frame_end(&mut self) {
+        unsafe {
+            sws_frame_end(self.as_mut_ptr());
+        }
+
+        if let Some(details) = self.frame_details.take() {
+            // These details were accepted before, restoring them cannot fail.
+            let _ = self.set_details(&details);
+        }
+    }
+
+    // Checks the frames and sets up the context for them, returning the
+    // colorspace details to restore after the conversion.
+    fn prepare_frame(
+        &mut self,
+        input: &frame::Video,
+        output: &mut frame::Video,
+    ) -> Result<Option<ColorspaceDetails>, Error> {
+        if input.format() != self.input.format
+            || input.width() != self.input.width
+            || input.height() != self.input.height
+        {
+            return Err(Error::InputChanged);
+        }
+
+        unsafe {
+            if output.is_empty() {
+                output.alloc(self.output.format, self.output.width, self.output.height);
+            }
+        }
+
+        if output.format() != self.output.format
+            || output.width() != self.output.width
+            || output.height() != self.output.height
+        {
+            return Err(Error::OutputChanged);
+        }
+
+        let previous = self.colorspace_details();
+        let mut details = previous;
+
+        if let Some(details) = details.as_mut() {
+            if input.color_space() != color::Space::Unspecified {
+                details.src_space = input.color_space().into();
+            }
+
+            if input.color_range() != color::Range::Unspecified {
+                details.src_range = input.color_range();
+            }
+
+            if output.color_range() != color::Range::Unspecified {
+                details.dst_range = output.color_range();
+            }
+
+            self.set_details(details)?;
+        }
+
+        output.set_pts(input.pts());
+        output.set_color_primaries(input.color_primaries());
+        output.set_color_transfer_characteristic(input.color_transfer_characteristic());
+
+        match details {
+            Some(_) if is_rgb(self.output.format) => {
+                output.set_color_space(color::Space::RGB);
+                output.set_color_range(color::Range::JPEG);
+            }
+
+            Some(details) if is_rgb(self.input.format) => {
+                output.set_color_space(details.dst_space.into());
+                output.set_color_range(details.dst_range);
+            }
+
+            _ => {
+                output.set_color_space(input.color_space());
+                output.set_color_range(input.color_range());
+            }
+        }
+
+        Ok(previous.filter(|&previous| Some(previous) != details))
+    }
+}
+
+fn is_rgb(format: format::Pixel) -> bool {
+    format
+        .descriptor()
+        .is_some_and(|desc| unsafe { (*desc.as_ptr()).flags } & AV_PIX_FMT_FLAG_RGB as u64 != 0)
+}
+
+impl AsPtr<SwsContext> for Context {
+    fn as_ptr(&self) -> *const SwsContext {
+        self.ptr as *const _
+    }
+}
+
+impl AsMutPtr<SwsContext> for Context {
+    fn as_mut_ptr(&mut self) -> *mut SwsContext {
+        self.ptr
+    }
+}
+
+impl option::Settable<SwsContext> for Context {}
+impl option::Gettable<SwsContext> for Context {}
+impl option::Iterable<SwsContext> for Context {}
+
 impl Drop for Context {
     fn drop(&mut self) {
         unsafe {
@@ -172,3 +475,54 @@ impl Drop for Context {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn details() {
+        let mut context = Context::get(
+            format::Pixel::YUV420P,
+            16,
+            16,
+            format::Pixel::RGB24,
+            16,
+            16,
+            Flags::BILINEAR,
+        )
+        .unwrap();
+
+        let details = ColorspaceDetails {
+            src_space: ColorSpace::ITU709,
+            src_range: color::Range::JPEG,
+            dst_space: ColorSpace::ITU601,
+            dst_range: color::Range::MPEG,
+            brightness: 0,
+            contrast: 1 << 16,
+            saturation: 1 << 16,
+        };
+
+        context.set_details(&details).unwrap();
+        assert_eq!(context.colorspace_details(), Some(details));
+
+        let mut input = frame::Video::new(format::Pixel::YUV420P, 16, 16);
+        for plane in 0..3 {
+            input.data_mut(plane).fill(128);
+        }
+        input.set_color_space(color::Space::BT470BG);
+        input.set_color_range(color::Range::MPEG);
+        input.set_pts(Some(42));
+
+        let mut output = frame::Video::empty();
+        context.run_frame(&input, &mut output).unwrap();
+
+        // Y = 128 in the MPEG range is slightly brighter than mid gray.
+        assert!(output.data(0)[..48]
+            .iter()
+            .all(|&v| (129..=132).contains(&v)));
+        assert_eq!(output.pts(), Some(42));
+        assert_eq!(output.color_range(), color::Range::JPEG);
+        assert_eq!(context.colorspace_details(), Some(details));
+    }
+}
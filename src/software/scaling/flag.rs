@@ -4,6 +4,7 @@ use libc::c_int;
 
 #[cfg(not(feature = "ffmpeg_8_0"))]
 bitflags::bitflags! {
+    #[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
     pub struct Flags: c_int {
         const FAST_BILINEAR        = SWS_FAST_BILINEAR;
         const BILINEAR             = SWS_BILINEAR;
@@ -34,6 +35,7 @@ use crate::ffi::SwsFlags as SF;
 
 #[cfg(feature = "ffmpeg_8_0")]
 bitflags::bitflags! {
+    #[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
     pub struct Flags: c_int {
         const FAST_BILINEAR        = SF::FAST_BILINEAR.0 as _;
         const BILINEAR             = SF::BILINEAR.0 as _;
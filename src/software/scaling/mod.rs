@@ -2,7 +2,7 @@ pub mod flag;
 pub use self::flag::Flags;
 
 pub mod color_space;
-pub use self::color_space::ColorSpace;
+pub use self::color_space::{ColorSpace, ColorspaceDetails};
 
 pub mod support;
 
@@ -13,7 +13,10 @@ pub mod filter;
 pub use self::filter::Filter;
 
 pub mod context;
-pub use self::context::Context;
+pub use self::context::{Context, Definition};
+
+pub mod cache;
+pub use self::cache::ScalerCache;
 
 mod extensions;
 
@@ -0,0 +1,275 @@
+use std::ffi::c_void;
+
+use crate::ffi::*;
+use crate::util::format;
+use crate::{frame, ChannelLayout, Error, Rational, Rescale};
+use libc::{c_int, EINVAL, ENOMEM};
+
+/// A FIFO of audio samples, used to turn frames of arbitrary size into frames
+/// of the size an encoder expects.
+///
+/// Planar and packed sample formats are supported. Output frames get
+/// continuous timestamps, starting at the pts of the first frame that was
+/// written, so gaps in the input timestamps are not preserved.
+pub struct AudioFifo {
+    ptr: *mut AVAudioFifo,
+
+    format: format::Sample,
+    layout: AVChannelLayout,
+    rate: u32,
+    time_base: Rational,
+
+    start_pts: Option<i64>,
+    samples_read: i64,
+}
+
+unsafe impl Send for AudioFifo {}
+
+impl AudioFifo {
+    #[inline(always)]
+    pub unsafe fn as_ptr(&self) -> *const AVAudioFifo {
+        self.ptr as *const _
+    }
+
+    #[inline(always)]
+    pub unsafe fn as_mut_ptr(&mut self) -> *mut AVAudioFifo {
+        self.ptr
+    }
+}
+
+impl AudioFifo {
+    /// Creates an empty FIFO for samples of the given format.
+    ///
+    /// Timestamps use a time base of `1 / rate` unless changed with
+    /// [`set_time_base`][Self::set_time_base].
+    pub fn new(format: format::Sample, layout: &ChannelLayout, rate: u32) -> Result<Self, Error> {
+        if format == format::Sample::None || rate == 0 {
+            return Err(Error::Other { errno: EINVAL });
+        }
+
+        unsafe {
+            let mut copy = AVChannelLayout::empty();
+
+            let ret = av_channel_layout_copy(&mut copy, layout.as_ptr());
+            if ret < 0 {
+                return Err(Error::from(ret));
+            }
+
+            let ptr = av_audio_fifo_alloc(format.into(), copy.nb_channels, 1);
+
+            if ptr.is_null() {
+                av_channel_layout_uninit(&mut copy);
+                return Err(Error::Other { errno: ENOMEM });
+            }
+
+            Ok(AudioFifo {
+                ptr,
+
+                format,
+                layout: copy,
+                rate,
+                time_base: Rational(1, rate as c_int),
+
+                start_pts: None,
+                samples_read: 0,
+            })
+        }
+    }
+
+    /// Creates an empty FIFO for frames like `frame`.
+    pub fn for_frame(frame: &frame::Audio) -> Result<Self, Error> {
+        Self::new(frame.format(), &frame.ch_layout(), frame.rate())
+    }
+
+    pub fn format(&self) -> format::Sample {
+        self.format
+    }
+
+    pub fn ch_layout(&self) -> ChannelLayout<'_> {
+        ChannelLayout::from(&self.layout)
+    }
+
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    pub fn time_base(&self) -> Rational {
+        self.time_base
+    }
+
+    /// Time base of the timestamps of written and read frames.
+    pub fn set_time_base<R: Into<Rational>>(&mut self, value: R) {
+        self.time_base = value.into();
+    }
+
+    /// Number of samples per channel in the FIFO.
+    pub fn samples(&self) -> usize {
+        unsafe { av_audio_fifo_size(self.ptr) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples() == 0
+    }
+
+    /// Appends all samples of `frame`.
+    ///
+    /// The pts of the first frame written to an empty FIFO defines the
+    /// timestamps of all frames read afterwards.
+    pub fn write(&mut self, frame: &frame::Audio) -> Result<(), Error> {
+        if frame.format() != self.format
+            || frame.ch_layout().channels() != self.layout.nb_channels as u32
+        {
+            return Err(Error::InputChanged);
+        }
+
+        if self.start_pts.is_none() {
+            if let Some(pts) = frame.pts() {
+                let buffered =
+                    (self.samples() as i64).rescale(self.sample_time_base(), self.time_base);
+                self.start_pts = Some(pts - buffered);
+                self.samples_read = 0;
+            }
+        }
+
+        unsafe {
+            match av_audio_fifo_write(
+                self.ptr,
+                (*frame.as_ptr()).extended_data as *const *mut c_void,
+                frame.samples() as c_int,
+            ) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Reads a frame of exactly `samples` samples, or `None` if the FIFO holds
+    /// fewer samples.
+    pub fn read(&mut self, samples: usize) -> Result<Option<frame::Audio>, Error> {
+        if samples == 0 || self.samples() < samples {
+            return Ok(None);
+        }
+
+        self.read_frame(samples).map(Some)
+    }
+
+    /// Reads all remaining samples into a single frame, or `None` if the FIFO
+    /// is empty. Useful for the last, shorter frame of a stream.
+    pub fn read_remaining(&mut self) -> Result<Option<frame::Audio>, Error> {
+        match self.samples() {
+            0 => Ok(None),
+            samples => self.read_frame(samples).map(Some),
+        }
+    }
+
+    /// Drops the first `samples` samples, advancing the timestamps accordingly.
+    pub fn discard(&mut self, samples: usize) -> Result<(), Error> {
+        let samples = samples.min(self.samples());
+
+        unsafe {
+            match av_audio_fifo_drain(self.ptr, samples as c_int) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => {
+                    self.samples_read += samples as i64;
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Drops all samples and forgets the timestamps.
+    pub fn reset(&mut self) {
+        unsafe {
+            av_audio_fifo_reset(self.ptr);
+        }
+
+        self.start_pts = None;
+        self.samples_read = 0;
+    }
+
+    fn sample_time_base(&self) -> Rational {
+        Rational(1, self.rate as c_int)
+    }
+
+    fn read_frame(&mut self, samples: usize) -> Result<frame::Audio, Error> {
+        let mut frame = frame::Audio::empty();
+        frame.set_format(self.format);
+        frame.set_samples(samples);
+        frame.set_rate(self.rate);
+
+        unsafe {
+            let ptr = frame.as_mut_ptr();
+
+            let ret = av_channel_layout_copy(&mut (*ptr).ch_layout, &self.layout);
+            if ret < 0 {
+                return Err(Error::from(ret));
+            }
+
+            let ret = av_frame_get_buffer(ptr, 0);
+            if ret < 0 {
+                return Err(Error::from(ret));
+            }
+
+            let ret = av_audio_fifo_read(
+                self.ptr,
+                (*ptr).extended_data as *const *mut c_void,
+                samples as c_int,
+            );
+            if ret < 0 {
+                return Err(Error::from(ret));
+            }
+        }
+
+        if let Some(start) = self.start_pts {
+            let offset = self
+                .samples_read
+                .rescale(self.sample_time_base(), self.time_base);
+            frame.set_pts(Some(start + offset));
+        }
+
+        self.samples_read += samples as i64;
+
+        Ok(frame)
+    }
+}
+
+impl Drop for AudioFifo {
+    fn drop(&mut self) {
+        unsafe {
+            av_audio_fifo_free(self.ptr);
+            av_channel_layout_uninit(&mut self.layout);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChannelLayoutMask;
+
+    #[test]
+    fn frames() {
+        let format = format::Sample::F32(format::sample::Type::Planar);
+        let mut fifo = AudioFifo::new(format, &ChannelLayout::STEREO, 48000).unwrap();
+
+        for (pts, samples) in [(0, 1000), (1000, 700)] {
+            let mut frame = frame::Audio::new(format, samples, ChannelLayoutMask::STEREO);
+            frame.set_rate(48000);
+            frame.set_pts(Some(pts));
+            fifo.write(&frame).unwrap();
+        }
+
+        assert_eq!(fifo.samples(), 1700);
+
+        let frame = fifo.read(1024).unwrap().unwrap();
+        assert_eq!(frame.samples(), 1024);
+        assert_eq!(frame.pts(), Some(0));
+
+        assert!(fifo.read(1024).unwrap().is_none());
+
+        let frame = fifo.read_remaining().unwrap().unwrap();
+        assert_eq!(frame.samples(), 676);
+        assert_eq!(frame.pts(), Some(1024));
+        assert!(fifo.is_empty());
+    }
+}
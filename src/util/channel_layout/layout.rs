@@ -3,7 +3,7 @@ use std::borrow::Cow;
 use std::ffi::CString;
 
 use crate::ffi::*;
-#[cfg(feature = "ffmpeg_7_0")]
+#[cfg(any(feature = "ffmpeg_7_0", feature = "software-resampling"))]
 use crate::Error;
 use libc::{c_int, c_uint};
 
@@ -247,6 +247,38 @@ impl<'a> ChannelLayout<'a> {
         unsafe { av_channel_layout_check(self.as_ptr()) != 0 }
     }
 
+    /// Map the channels of `target` to the channels of this layout, e.g. to
+    /// convert audio data between a native and a custom channel order.
+    ///
+    /// The element `i` of the result is the index in this layout of channel
+    /// `i` of `target`, or `None` if this layout does not contain that channel.
+    /// Layouts with an unspecified order are mapped by position.
+    pub fn reorder(&self, target: &ChannelLayout) -> Vec<Option<u32>> {
+        let positional = self.order() == ChannelOrder::Unspecified
+            || target.order() == ChannelOrder::Unspecified;
+
+        (0..target.channels())
+            .map(|idx| {
+                if positional {
+                    Some(idx).filter(|&idx| idx < self.channels())
+                } else {
+                    self.index_from_channel(target.channel_from_index(idx))
+                }
+            })
+            .collect()
+    }
+
+    /// Build the matrix to mix audio in this layout into `target`, e.g. for
+    /// [`resampling::Context::set_matrix`][crate::software::resampling::Context::set_matrix].
+    #[cfg(feature = "software-resampling")]
+    pub fn mix_matrix_to(
+        &self,
+        target: &ChannelLayout,
+        options: crate::software::resampling::MixOptions,
+    ) -> Result<crate::software::resampling::Matrix, Error> {
+        crate::software::resampling::Matrix::build_with_options(self, target, options)
+    }
+
     /// Change the [`ChannelOrder`] of this channel layout. If the current layout is borrowed,
     /// calling this function will clone the contained [`AVChannelLayout`].
     ///
@@ -418,6 +450,28 @@ mod test {
         assert_eq!(custom.map().unwrap(), &channels);
     }
 
+    #[test]
+    fn reorder() {
+        let custom = ChannelLayout::custom(vec![
+            ChannelCustom::new(Channel::LowFrequency),
+            ChannelCustom::new(Channel::FrontRight),
+            ChannelCustom::new(Channel::FrontLeft),
+        ]);
+
+        assert_eq!(
+            custom.reorder(&ChannelLayout::_2POINT1),
+            [Some(2), Some(1), Some(0)]
+        );
+        assert_eq!(
+            ChannelLayout::STEREO.reorder(&custom),
+            [None, Some(1), Some(0)]
+        );
+        assert_eq!(
+            ChannelLayout::unspecified(1).reorder(&ChannelLayout::STEREO),
+            [Some(0), None]
+        );
+    }
+
     #[test]
     fn defaults() {
         let unspec = ChannelLayout::default_for_channels(0);
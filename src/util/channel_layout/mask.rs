@@ -2,7 +2,7 @@ use crate::ffi::*;
 use libc::c_ulonglong;
 
 bitflags::bitflags! {
-    #[derive(Eq, PartialEq, Copy, Clone, Debug)]
+    #[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
     pub struct ChannelLayoutMask: c_ulonglong {
         const FRONT_LEFT            = AV_CH_FRONT_LEFT;
         const FRONT_RIGHT           = AV_CH_FRONT_RIGHT;
@@ -40,7 +40,7 @@ pub unsafe fn get<'d>(dict: *const AVDictionary, key: &str, flags: Flags) -> Opt
 }
 
 impl_for_many! {
-    impl for Dictionary, DictionaryRef<'a>, DictionaryMut<'a> {
+    impl for Dictionary, DictionaryMut<'a> {
         /// Try to find a value in the dictionary.
         ///
         /// This function uses case-insensitive matching of the entire key string
@@ -59,7 +59,32 @@ impl_for_many! {
             // SAFETY: Returned lifetime is bounded by borrow on self
             unsafe { get(self.as_ptr(), key.as_ref(), flags) }
         }
+    }
+}
+
+impl<'d> DictionaryRef<'d> {
+    /// Try to find a value in the dictionary.
+    ///
+    /// This function uses case-insensitive matching of the entire key string
+    /// to find a value. If you want to customize the way FFmpeg searches
+    /// for the key, see [`get_with_flags`][Self::get_with_flags].
+    pub fn get<K: AsRef<str>>(&self, key: K) -> Option<&'d str> {
+        self.get_with_flags(key, Flags::empty())
+    }
 
+    /// Try to find a value in the dictionary, using custom search flags.
+    ///
+    /// See [Flags][crate::dictionary::Flags] to see how each flag works.
+    /// Using [`Flags::DONT_STRDUP_KEY`] is heavily discouraged unless you
+    /// know what you are doing.
+    pub fn get_with_flags<K: AsRef<str>>(&self, key: K, flags: Flags) -> Option<&'d str> {
+        // SAFETY: The referenced dictionary outlives 'd
+        unsafe { get(self.as_ptr(), key.as_ref(), flags) }
+    }
+}
+
+impl_for_many! {
+    impl for Dictionary, DictionaryRef<'a>, DictionaryMut<'a> {
         /// Returns the number of entries in the dictionary.
         pub fn len(&self) -> usize {
             unsafe { av_dict_count(self.as_ptr()) as usize }
@@ -10,10 +10,11 @@ use std::str::FromStr;
 
 use crate::ffi::*;
 use crate::utils;
+use libc::c_int;
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum Pixel {
     None,
@@ -489,6 +490,76 @@ pub struct Descriptor {
 unsafe impl Send for Descriptor {}
 unsafe impl Sync for Descriptor {}
 
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+    pub struct Flags: u64 {
+        /// Big-endian components.
+        const BE        = AV_PIX_FMT_FLAG_BE as u64;
+        /// The second plane holds a palette.
+        const PAL       = AV_PIX_FMT_FLAG_PAL as u64;
+        /// All values of a component are bit-wise packed end to end.
+        const BITSTREAM = AV_PIX_FMT_FLAG_BITSTREAM as u64;
+        /// Hardware accelerated format.
+        const HWACCEL   = AV_PIX_FMT_FLAG_HWACCEL as u64;
+        /// At least one component is stored in a separate plane.
+        const PLANAR    = AV_PIX_FMT_FLAG_PLANAR as u64;
+        /// The components are RGB-like, not YUV.
+        const RGB       = AV_PIX_FMT_FLAG_RGB as u64;
+        /// The format has an alpha channel.
+        const ALPHA     = AV_PIX_FMT_FLAG_ALPHA as u64;
+        /// Bayer pattern, as used by sensors.
+        const BAYER     = AV_PIX_FMT_FLAG_BAYER as u64;
+        /// Floating point components.
+        const FLOAT     = AV_PIX_FMT_FLAG_FLOAT as u64;
+    }
+}
+
+bitflags::bitflags! {
+    /// Information lost when converting between two formats.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+    pub struct Loss: c_int {
+        const RESOLUTION        = FF_LOSS_RESOLUTION as c_int;
+        const DEPTH             = FF_LOSS_DEPTH as c_int;
+        const COLORSPACE        = FF_LOSS_COLORSPACE as c_int;
+        const ALPHA             = FF_LOSS_ALPHA as c_int;
+        const COLORQUANT        = FF_LOSS_COLORQUANT as c_int;
+        const CHROMA            = FF_LOSS_CHROMA as c_int;
+        #[cfg(feature = "ffmpeg_6_1")]
+        const EXCESS_RESOLUTION = FF_LOSS_EXCESS_RESOLUTION as c_int;
+        #[cfg(feature = "ffmpeg_6_1")]
+        const EXCESS_DEPTH      = FF_LOSS_EXCESS_DEPTH as c_int;
+    }
+}
+
+/// Location of one component (e.g. Y, U, V or R, G, B, A) of a format.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Component {
+    /// Plane holding the component.
+    pub plane: u32,
+    /// Number of bytes (or bits for bitstream formats) between two pixels.
+    pub step: u32,
+    /// Number of bytes (or bits) before the component of the first pixel.
+    pub offset: u32,
+    /// Number of least significant bits to shift away to get the value.
+    pub shift: u32,
+    /// Number of bits of the component.
+    pub depth: u32,
+}
+
+impl From<AVComponentDescriptor> for Component {
+    fn from(value: AVComponentDescriptor) -> Self {
+        Component {
+            plane: value.plane as u32,
+            step: value.step as u32,
+            offset: value.offset as u32,
+            shift: value.shift as u32,
+            depth: value.depth as u32,
+        }
+    }
+}
+
 impl Pixel {
     pub const Y400A: Pixel = Pixel::YA8;
     pub const GRAY8A: Pixel = Pixel::YA8;
@@ -500,6 +571,73 @@ impl Pixel {
             ptr.as_ref().map(|ptr| Descriptor { ptr })
         }
     }
+
+    /// Descriptors of all pixel formats known to FFmpeg.
+    pub fn descriptors() -> DescriptorIter {
+        DescriptorIter {
+            ptr: std::ptr::null(),
+        }
+    }
+
+    /// The same format with the opposite endianness, e.g. `RGB48LE` for
+    /// `RGB48BE`, or `None` if there is no such format.
+    pub fn swap_endianness(self) -> Option<Pixel> {
+        match Pixel::from(unsafe { av_pix_fmt_swap_endianness(self.into()) }) {
+            Pixel::None => None,
+            format => Some(format),
+        }
+    }
+
+    /// Information lost when converting from this format to `dst`.
+    pub fn loss(self, dst: Pixel, has_alpha: bool) -> Loss {
+        unsafe {
+            Loss::from_bits_retain(av_get_pix_fmt_loss(
+                dst.into(),
+                self.into(),
+                c_int::from(has_alpha),
+            ))
+        }
+    }
+
+    /// The best of two formats to convert this format to, with the
+    /// information lost by the conversion.
+    pub fn find_best_of_2(self, dst1: Pixel, dst2: Pixel, has_alpha: bool) -> (Pixel, Loss) {
+        unsafe {
+            let mut loss = 0;
+            let format = av_find_best_pix_fmt_of_2(
+                dst1.into(),
+                dst2.into(),
+                self.into(),
+                c_int::from(has_alpha),
+                &mut loss,
+            );
+
+            (Pixel::from(format), Loss::from_bits_retain(loss))
+        }
+    }
+
+    /// The best format of `list` to convert this format to, with the
+    /// information lost by the conversion.
+    #[cfg(feature = "codec")]
+    pub fn find_best_of_list(self, list: &[Pixel], has_alpha: bool) -> (Pixel, Loss) {
+        let list: Vec<AVPixelFormat> = list
+            .iter()
+            .map(|&format| format.into())
+            .chain(std::iter::once(AVPixelFormat::NONE))
+            .collect();
+
+        unsafe {
+            let mut loss = 0;
+            let format = avcodec_find_best_pix_fmt_of_list(
+                list.as_ptr(),
+                self.into(),
+                c_int::from(has_alpha),
+                &mut loss,
+            );
+
+            (Pixel::from(format), Loss::from_bits_retain(loss))
+        }
+    }
 }
 
 impl Descriptor {
@@ -507,10 +645,20 @@ impl Descriptor {
         self.ptr
     }
 
+    /// The pixel format described.
+    pub fn format(self) -> Pixel {
+        unsafe { Pixel::from(av_pix_fmt_desc_get_id(self.as_ptr())) }
+    }
+
     pub fn name(self) -> &'static str {
         unsafe { utils::str_from_c_ptr((*self.as_ptr()).name) }
     }
 
+    /// Alternative comma-separated names of the format.
+    pub fn alias(self) -> Option<&'static str> {
+        unsafe { utils::optional_str_from_c_ptr((*self.as_ptr()).alias) }
+    }
+
     pub fn nb_components(self) -> u8 {
         unsafe { (*self.as_ptr()).nb_components }
     }
@@ -522,6 +670,61 @@ impl Descriptor {
     pub fn log2_chroma_h(self) -> u8 {
         unsafe { (*self.as_ptr()).log2_chroma_h }
     }
+
+    pub fn flags(self) -> Flags {
+        unsafe { Flags::from_bits_truncate((*self.as_ptr()).flags) }
+    }
+
+    pub fn component(self, index: usize) -> Option<Component> {
+        if index < self.nb_components() as usize {
+            unsafe { Some(Component::from((*self.as_ptr()).comp[index])) }
+        } else {
+            None
+        }
+    }
+
+    pub fn components(self) -> impl ExactSizeIterator<Item = Component> {
+        (0..self.nb_components() as usize).map(move |index| self.component(index).unwrap())
+    }
+
+    /// Number of bits per pixel, ignoring the padding and the chroma
+    /// subsampled planes.
+    pub fn bits_per_pixel(self) -> u32 {
+        unsafe { av_get_bits_per_pixel(self.as_ptr()) as u32 }
+    }
+
+    /// Number of bits per pixel, including the padding.
+    pub fn padded_bits_per_pixel(self) -> u32 {
+        unsafe { av_get_padded_bits_per_pixel(self.as_ptr()) as u32 }
+    }
+}
+
+impl fmt::Debug for Descriptor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Descriptor")
+            .field("name", &self.name())
+            .field("flags", &self.flags())
+            .field("log2_chroma_w", &self.log2_chroma_w())
+            .field("log2_chroma_h", &self.log2_chroma_h())
+            .field("components", &self.components().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Iterator over all pixel format descriptors.
+pub struct DescriptorIter {
+    ptr: *const AVPixFmtDescriptor,
+}
+
+impl Iterator for DescriptorIter {
+    type Item = Descriptor;
+
+    fn next(&mut self) -> Option<Descriptor> {
+        unsafe {
+            self.ptr = av_pix_fmt_desc_next(self.ptr);
+            self.ptr.as_ref().map(|ptr| Descriptor { ptr })
+        }
+    }
 }
 
 impl From<AVPixelFormat> for Pixel {
@@ -1448,10 +1651,43 @@ impl FromStr for Pixel {
         let cstring = CString::new(s)?;
         let format = unsafe { av_get_pix_fmt(cstring.as_ptr()) }.into();
 
-        if format == Pixel::None {
-            Err(ParsePixelError::UnknownFormat)
-        } else {
-            Ok(format)
+        if format != Pixel::None {
+            return Ok(format);
         }
+
+        // Fall back to a case-insensitive search of the names and aliases.
+        Pixel::descriptors()
+            .find(|desc| {
+                desc.name().eq_ignore_ascii_case(s)
+                    || desc
+                        .alias()
+                        .is_some_and(|alias| alias.split(',').any(|a| a.eq_ignore_ascii_case(s)))
+            })
+            .map(Descriptor::format)
+            .ok_or(ParsePixelError::UnknownFormat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn descriptor() {
+        let desc = Pixel::NV12.descriptor().unwrap();
+
+        assert!(desc.flags().contains(Flags::PLANAR));
+        assert_eq!(desc.bits_per_pixel(), 12);
+        assert_eq!(desc.component(1).unwrap().plane, 1);
+        assert_eq!(desc.component(2).unwrap().offset, 1);
+
+        assert_eq!(Pixel::RGB48BE.swap_endianness(), Some(Pixel::RGB48LE));
+        assert_eq!(Pixel::RGB24.swap_endianness(), None);
+        assert_eq!("Yuv420P".parse::<Pixel>().unwrap(), Pixel::YUV420P);
+        assert!(Pixel::descriptors().any(|desc| desc.name() == "rgba"));
+
+        assert!(Pixel::RGBA.loss(Pixel::RGB24, true).contains(Loss::ALPHA));
+        let (best, _) = Pixel::YUV420P.find_best_of_2(Pixel::NV12, Pixel::RGB24, false);
+        assert_eq!(best, Pixel::NV12);
     }
 }
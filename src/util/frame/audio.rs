@@ -1,12 +1,13 @@
+use std::marker::PhantomData;
 use std::mem;
 use std::ops::{Deref, DerefMut};
-use std::slice;
+use std::{ptr, slice};
 
 use super::Frame;
 use crate::ffi::*;
 use crate::util::format;
-use crate::ChannelLayoutMask;
-use libc::c_int;
+use crate::{ChannelLayoutMask, Error};
+use libc::{c_int, EINVAL};
 
 use crate::ChannelLayout;
 
@@ -226,6 +227,247 @@ impl Audio {
             )
         }
     }
+
+    /// Converts all samples to another sample format, keeping the channel
+    /// layout, rate and properties of the frame.
+    ///
+    /// Integer samples are scaled to and from the `[-1.0, 1.0]` range of
+    /// floating point samples, out of range values are clipped.
+    pub fn to_format(&self, format: format::Sample) -> Result<Audio, Error> {
+        let mut output = unsafe { Audio::alloc_like(format, self.samples(), self)? };
+
+        unsafe {
+            let ret = av_frame_copy_props(output.as_mut_ptr(), self.as_ptr());
+            if ret < 0 {
+                return Err(Error::from(ret));
+            }
+        }
+
+        let source = self.format();
+
+        for channel in 0..self.channel_count() {
+            for index in 0..self.samples() {
+                unsafe {
+                    let value = load(source, self.sample_ptr(channel, index));
+                    store(format, output.sample_ptr(channel, index) as *mut u8, value);
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Copies the samples into a frame with the channel layout `layout`,
+    /// keeping the format, rate and properties of the frame.
+    ///
+    /// Channels are matched with [`ChannelLayout::reorder`], channels of
+    /// `layout` missing from the frame are silent.
+    pub fn reorder(&self, layout: &ChannelLayout) -> Result<Audio, Error> {
+        if self.channel_count() == 0 || !layout.is_valid() {
+            return Err(Error::Other { errno: EINVAL });
+        }
+
+        let map = self.ch_layout().reorder(layout);
+        let bytes = self.format().bytes();
+
+        unsafe {
+            let mut like = Audio::empty();
+            like.set_rate(self.rate());
+
+            let ret = av_channel_layout_copy(&mut (*like.as_mut_ptr()).ch_layout, layout.as_ptr());
+            if ret < 0 {
+                return Err(Error::from(ret));
+            }
+
+            let mut output = Audio::alloc_like(self.format(), self.samples(), &like)?;
+
+            let ret = av_frame_copy_props(output.as_mut_ptr(), self.as_ptr());
+            if ret < 0 {
+                return Err(Error::from(ret));
+            }
+
+            av_samples_set_silence(
+                (*output.as_mut_ptr()).extended_data,
+                0,
+                self.samples() as c_int,
+                layout.channels() as c_int,
+                self.format().into(),
+            );
+
+            for (channel, source) in map.into_iter().enumerate() {
+                let Some(source) = source else { continue };
+
+                for index in 0..self.samples() {
+                    ptr::copy_nonoverlapping(
+                        self.sample_ptr(source as usize, index),
+                        output.sample_ptr(channel, index) as *mut u8,
+                        bytes,
+                    );
+                }
+            }
+
+            Ok(output)
+        }
+    }
+
+    /// Creates a packed frame from interleaved samples, whose format is the
+    /// packed format of `T`.
+    pub fn from_interleaved<T: Scalar>(
+        samples: &[T],
+        layout: &ChannelLayout,
+        rate: u32,
+    ) -> Result<Audio, Error> {
+        let channels = layout.channels() as usize;
+
+        if channels == 0 || samples.len() % channels != 0 {
+            return Err(Error::Other { errno: EINVAL });
+        }
+
+        unsafe {
+            let mut frame = Audio::empty();
+            frame.set_rate(rate);
+
+            let ret = av_channel_layout_copy(&mut (*frame.as_mut_ptr()).ch_layout, layout.as_ptr());
+            if ret < 0 {
+                return Err(Error::from(ret));
+            }
+
+            let mut frame = Audio::alloc_like(T::FORMAT, samples.len() / channels, &frame)?;
+
+            ptr::copy_nonoverlapping(
+                samples.as_ptr(),
+                (*frame.as_mut_ptr()).data[0] as *mut T,
+                samples.len(),
+            );
+
+            Ok(frame)
+        }
+    }
+
+    /// All samples interleaved (`[c0, c1, c0, c1, ...]`), converted to `T` if
+    /// the frame has another sample format.
+    pub fn interleaved<T: Scalar>(&self) -> Vec<T> {
+        let channels = self.channel_count();
+
+        if self.format() == T::FORMAT && self.planes() == 1 {
+            unsafe {
+                return slice::from_raw_parts(
+                    (*self.as_ptr()).data[0] as *const T,
+                    self.samples() * channels,
+                )
+                .to_vec();
+            }
+        }
+
+        let mut samples = Vec::with_capacity(self.samples() * channels);
+
+        for index in 0..self.samples() {
+            for channel in 0..channels {
+                samples.push(unsafe { read(self.format(), self.sample_ptr(channel, index)) });
+            }
+        }
+
+        samples
+    }
+
+    /// Iterates over the samples of `channel`, converted to `T` if the frame
+    /// has another sample format. Works for both planar and packed frames.
+    pub fn channel<T: Scalar>(&self, channel: usize) -> ChannelIter<'_, T> {
+        if channel >= self.channel_count() {
+            panic!("out of bounds");
+        }
+
+        ChannelIter {
+            frame: self,
+            channel,
+            index: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Highest absolute sample value, on a linear scale where 1.0 is full
+    /// scale.
+    pub fn peak(&self) -> f64 {
+        let mut peak = 0.0f64;
+
+        for channel in 0..self.channel_count() {
+            for value in self.channel::<f64>(channel) {
+                peak = peak.max(value.abs());
+            }
+        }
+
+        peak
+    }
+
+    /// Root mean square of all samples of all channels, on a linear scale
+    /// where 1.0 is full scale.
+    pub fn rms(&self) -> f64 {
+        let count = self.samples() * self.channel_count();
+
+        if count == 0 {
+            return 0.0;
+        }
+
+        let mut sum = 0.0;
+
+        for channel in 0..self.channel_count() {
+            for value in self.channel::<f64>(channel) {
+                sum += value * value;
+            }
+        }
+
+        (sum / count as f64).sqrt()
+    }
+
+    fn channel_count(&self) -> usize {
+        if self.planes() == 0 {
+            0
+        } else {
+            self.ch_layout().channels() as usize
+        }
+    }
+
+    // Address of a sample, for both planar and packed layouts.
+    unsafe fn sample_ptr(&self, channel: usize, index: usize) -> *const u8 {
+        let bytes = self.format().bytes();
+
+        if self.is_planar() {
+            (*(*self.as_ptr()).extended_data.add(channel)).add(index * bytes)
+        } else {
+            (*self.as_ptr()).data[0].add((index * self.channel_count() + channel) * bytes)
+        }
+    }
+
+    // Allocates a frame with the channel layout and rate of `like`.
+    unsafe fn alloc_like(
+        format: format::Sample,
+        samples: usize,
+        like: &Audio,
+    ) -> Result<Audio, Error> {
+        if format == format::Sample::None {
+            return Err(Error::Other { errno: EINVAL });
+        }
+
+        let mut frame = Audio::empty();
+        frame.set_format(format);
+        frame.set_samples(samples);
+        frame.set_rate(like.rate());
+
+        let ret = av_channel_layout_copy(
+            &mut (*frame.as_mut_ptr()).ch_layout,
+            &(*like.as_ptr()).ch_layout,
+        );
+        if ret < 0 {
+            return Err(Error::from(ret));
+        }
+
+        let ret = av_frame_get_buffer(frame.as_mut_ptr(), 0);
+        if ret < 0 {
+            return Err(Error::from(ret));
+        }
+
+        Ok(frame)
+    }
 }
 
 impl Deref for Audio {
@@ -279,6 +521,12 @@ impl From<Frame> for Audio {
     }
 }
 
+impl From<Audio> for Frame {
+    fn from(frame: Audio) -> Self {
+        frame.0
+    }
+}
+
 pub unsafe trait Sample {
     fn is_valid(format: format::Sample, channels: u16) -> bool;
 }
@@ -527,3 +775,210 @@ unsafe impl Sample for (f64, f64, f64, f64, f64, f64, f64) {
         channels == 7 && format == format::Sample::F64(format::sample::Type::Packed)
     }
 }
+
+/// A sample value which can be read from and written to frames of any sample
+/// format, through a normalized `f64` in the `[-1.0, 1.0]` range.
+///
+/// # Safety
+///
+/// Samples in [`FORMAT`][Self::FORMAT] are read and written as `Self`, so a
+/// value of that format must have the size and in-memory representation of
+/// `Self`, and every bit pattern of that size must be a valid `Self`.
+pub unsafe trait Scalar: Copy {
+    /// Packed sample format storing values of this type.
+    const FORMAT: format::Sample;
+
+    fn to_f64(self) -> f64;
+    fn from_f64(value: f64) -> Self;
+}
+
+unsafe impl Scalar for u8 {
+    const FORMAT: format::Sample = format::Sample::U8(format::sample::Type::Packed);
+
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        (f64::from(self) - 128.0) / 128.0
+    }
+
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        (value * 128.0 + 128.0).round().clamp(0.0, 255.0) as u8
+    }
+}
+
+unsafe impl Scalar for i16 {
+    const FORMAT: format::Sample = format::Sample::I16(format::sample::Type::Packed);
+
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        f64::from(self) / 32768.0
+    }
+
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        (value * 32768.0).round().clamp(-32768.0, 32767.0) as i16
+    }
+}
+
+unsafe impl Scalar for i32 {
+    const FORMAT: format::Sample = format::Sample::I32(format::sample::Type::Packed);
+
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        f64::from(self) / 2147483648.0
+    }
+
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        (value * 2147483648.0)
+            .round()
+            .clamp(-2147483648.0, 2147483647.0) as i32
+    }
+}
+
+unsafe impl Scalar for i64 {
+    const FORMAT: format::Sample = format::Sample::I64(format::sample::Type::Packed);
+
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        self as f64 / 9223372036854775808.0
+    }
+
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        // Float to integer casts saturate.
+        (value * 9223372036854775808.0).round() as i64
+    }
+}
+
+unsafe impl Scalar for f32 {
+    const FORMAT: format::Sample = format::Sample::F32(format::sample::Type::Packed);
+
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+unsafe impl Scalar for f64 {
+    const FORMAT: format::Sample = format::Sample::F64(format::sample::Type::Packed);
+
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+unsafe fn load(format: format::Sample, ptr: *const u8) -> f64 {
+    use format::Sample as S;
+
+    match format {
+        S::U8(..) => ptr::read(ptr).to_f64(),
+        S::I16(..) => ptr::read(ptr as *const i16).to_f64(),
+        S::I32(..) => ptr::read(ptr as *const i32).to_f64(),
+        S::I64(..) => ptr::read(ptr as *const i64).to_f64(),
+        S::F32(..) => ptr::read(ptr as *const f32).to_f64(),
+        S::F64(..) => ptr::read(ptr as *const f64),
+        S::None => 0.0,
+    }
+}
+
+unsafe fn store(format: format::Sample, ptr: *mut u8, value: f64) {
+    use format::Sample as S;
+
+    match format {
+        S::U8(..) => ptr::write(ptr, u8::from_f64(value)),
+        S::I16(..) => ptr::write(ptr as *mut i16, i16::from_f64(value)),
+        S::I32(..) => ptr::write(ptr as *mut i32, i32::from_f64(value)),
+        S::I64(..) => ptr::write(ptr as *mut i64, i64::from_f64(value)),
+        S::F32(..) => ptr::write(ptr as *mut f32, f32::from_f64(value)),
+        S::F64(..) => ptr::write(ptr as *mut f64, value),
+        S::None => (),
+    }
+}
+
+// Reads a sample as `T`, without going through `f64` if the types match.
+unsafe fn read<T: Scalar>(format: format::Sample, ptr: *const u8) -> T {
+    if format.packed() == T::FORMAT {
+        ptr::read(ptr as *const T)
+    } else {
+        T::from_f64(load(format, ptr))
+    }
+}
+
+/// Iterator over the samples of one channel of a [`Audio`] frame.
+pub struct ChannelIter<'a, T> {
+    frame: &'a Audio,
+    channel: usize,
+    index: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T: Scalar> Iterator for ChannelIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.index >= self.frame.samples() {
+            return None;
+        }
+
+        let value = unsafe {
+            read(
+                self.frame.format(),
+                self.frame.sample_ptr(self.channel, self.index),
+            )
+        };
+        self.index += 1;
+
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.frame.samples().saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T: Scalar> ExactSizeIterator for ChannelIter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Channel, ChannelCustom};
+
+    #[test]
+    fn reorder() {
+        let samples: Vec<i16> = vec![1, 2, 3, 4, 5, 6];
+        let packed = Audio::from_interleaved(&samples, &ChannelLayout::STEREO, 48000).unwrap();
+        let planar = packed
+            .to_format(format::Sample::I16(format::sample::Type::Planar))
+            .unwrap();
+
+        let layout = ChannelLayout::custom(vec![
+            ChannelCustom::new(Channel::FrontRight),
+            ChannelCustom::new(Channel::FrontLeft),
+            ChannelCustom::new(Channel::FrontCenter),
+        ]);
+
+        let reordered = packed.reorder(&layout).unwrap();
+        assert!(reordered.is_packed());
+        assert_eq!(reordered.ch_layout(), layout);
+        assert_eq!(reordered.interleaved::<i16>(), [2, 1, 0, 4, 3, 0, 6, 5, 0]);
+
+        let reordered = planar.reorder(&layout).unwrap();
+        assert!(reordered.is_planar());
+        assert_eq!(reordered.channel::<i16>(0).collect::<Vec<_>>(), [2, 4, 6]);
+        assert_eq!(reordered.channel::<i16>(1).collect::<Vec<_>>(), [1, 3, 5]);
+        assert_eq!(reordered.channel::<i16>(2).collect::<Vec<_>>(), [0, 0, 0]);
+    }
+}
@@ -0,0 +1,128 @@
+use std::ops::Deref;
+
+use ::image::{GrayImage, ImageBuffer, Pixel as ImagePixel, RgbImage, RgbaImage};
+
+use super::Video;
+use crate::util::format::Pixel;
+use crate::Error;
+
+// Copies the visible pixels of a packed 8-bit frame.
+fn to_buffer<P>(frame: &Video, format: Pixel) -> Result<ImageBuffer<P, Vec<u8>>, Error>
+where
+    P: ImagePixel<Subpixel = u8>,
+{
+    let frame = frame.converted(format)?;
+    let view = frame.plane_view::<u8>(0);
+
+    let mut data = Vec::with_capacity(view.width() * view.height());
+
+    for row in view.rows() {
+        data.extend_from_slice(row);
+    }
+
+    Ok(ImageBuffer::from_raw(frame.width(), frame.height(), data)
+        .expect("buffer matches the frame size"))
+}
+
+fn from_buffer<P, C>(image: &ImageBuffer<P, C>, format: Pixel) -> Video
+where
+    P: ImagePixel<Subpixel = u8>,
+    C: Deref<Target = [u8]>,
+{
+    let mut frame = Video::new(format, image.width(), image.height());
+    let row_len = image.width() as usize * P::CHANNEL_COUNT as usize;
+
+    if row_len > 0 {
+        let mut view = frame.plane_view_mut::<u8>(0);
+
+        for (row, source) in view.rows_mut().zip(image.as_raw().chunks_exact(row_len)) {
+            row.copy_from_slice(source);
+        }
+    }
+
+    frame
+}
+
+/// Converts the frame to RGB24 first if it has another format, which requires
+/// the `software-scaling` feature.
+impl TryFrom<&Video> for RgbImage {
+    type Error = Error;
+
+    fn try_from(frame: &Video) -> Result<Self, Error> {
+        to_buffer(frame, Pixel::RGB24)
+    }
+}
+
+/// Converts the frame to RGBA first if it has another format, which requires
+/// the `software-scaling` feature.
+impl TryFrom<&Video> for RgbaImage {
+    type Error = Error;
+
+    fn try_from(frame: &Video) -> Result<Self, Error> {
+        to_buffer(frame, Pixel::RGBA)
+    }
+}
+
+/// Converts the frame to GRAY8 first if it has another format, which requires
+/// the `software-scaling` feature.
+impl TryFrom<&Video> for GrayImage {
+    type Error = Error;
+
+    fn try_from(frame: &Video) -> Result<Self, Error> {
+        to_buffer(frame, Pixel::GRAY8)
+    }
+}
+
+impl From<&RgbImage> for Video {
+    fn from(image: &RgbImage) -> Self {
+        from_buffer(image, Pixel::RGB24)
+    }
+}
+
+impl From<RgbImage> for Video {
+    fn from(image: RgbImage) -> Self {
+        Video::from(&image)
+    }
+}
+
+impl From<&RgbaImage> for Video {
+    fn from(image: &RgbaImage) -> Self {
+        from_buffer(image, Pixel::RGBA)
+    }
+}
+
+impl From<RgbaImage> for Video {
+    fn from(image: RgbaImage) -> Self {
+        Video::from(&image)
+    }
+}
+
+impl From<&GrayImage> for Video {
+    fn from(image: &GrayImage) -> Self {
+        from_buffer(image, Pixel::GRAY8)
+    }
+}
+
+impl From<GrayImage> for Video {
+    fn from(image: GrayImage) -> Self {
+        Video::from(&image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgb_round_trip() {
+        // An odd width, so that the rows of the frame are padded.
+        let image = RgbImage::from_fn(5, 3, |x, y| ::image::Rgb([x as u8, y as u8, 42]));
+
+        let frame = Video::from(&image);
+        assert_eq!(frame.format(), Pixel::RGB24);
+        assert_eq!((frame.width(), frame.height()), (5, 3));
+        assert!(frame.stride(0) > 15);
+
+        assert_eq!(RgbImage::try_from(&frame).unwrap(), image);
+    }
+}
@@ -2,18 +2,26 @@ pub mod side_data;
 pub use self::side_data::SideData;
 
 pub mod video;
-pub use self::video::Video;
+pub use self::video::{Rect, Video};
+
+pub mod plane;
+pub use self::plane::{PlaneView, PlaneViewMut};
 
 pub mod audio;
-pub use self::audio::Audio;
+pub use self::audio::{Audio, ChannelIter, Scalar};
 
 pub mod flag;
 pub use self::flag::Flags;
 
+#[cfg(feature = "image")]
+mod image;
+#[cfg(feature = "ndarray")]
+mod ndarray;
+
 use crate::ffi::*;
 #[cfg(feature = "ffmpeg_8_1")]
 use crate::format::AlphaMode;
-use crate::{DictionaryMut, DictionaryRef};
+use crate::{DictionaryMut, DictionaryRef, Error, Rational, Timestamp};
 
 #[derive(PartialEq, Eq)]
 pub struct Frame {
@@ -104,6 +112,13 @@ impl Frame {
         }
     }
 
+    /// The best effort timestamp, in `time_base`, which is usually the time
+    /// base of the decoder or of the stream.
+    #[inline]
+    pub fn timestamp_in<T: Into<Rational>>(&self, time_base: T) -> Option<Timestamp> {
+        self.timestamp().map(|t| Timestamp::new(t, time_base))
+    }
+
     #[inline]
     pub fn quality(&self) -> usize {
         unsafe { (*self.as_ptr()).quality as usize }
@@ -155,6 +170,34 @@ impl Frame {
         }
     }
 
+    /// Copies the metadata fields (timestamps, side data, color properties,
+    /// ...) of `source`, but not the data.
+    pub fn copy_props(&mut self, source: &Frame) -> Result<(), Error> {
+        unsafe {
+            match av_frame_copy_props(self.as_mut_ptr(), source.as_ptr()) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Whether the data buffers can be written to, i.e. they are not shared
+    /// with other frames.
+    pub fn is_writable(&self) -> bool {
+        unsafe { av_frame_is_writable(self.as_ptr() as *mut _) != 0 }
+    }
+
+    /// Ensures the data buffers are writable, copying them if they are shared
+    /// with other frames.
+    pub fn make_writable(&mut self) -> Result<(), Error> {
+        unsafe {
+            match av_frame_make_writable(self.as_mut_ptr()) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
     #[cfg(feature = "ffmpeg_8_1")]
     pub fn alpha_mode(&self) -> AlphaMode {
         unsafe { (*self.as_ptr()).alpha_mode.into() }
@@ -0,0 +1,128 @@
+use ::ndarray::{Array3, ArrayView3, ArrayViewMut3, ShapeBuilder};
+
+use super::Video;
+use crate::util::format::Pixel;
+use crate::Error;
+use libc::EINVAL;
+
+// Number of bytes per pixel of packed formats with 8-bit components.
+fn channels(format: Pixel) -> Option<usize> {
+    match format {
+        Pixel::GRAY8 => Some(1),
+        Pixel::RGB24 | Pixel::BGR24 => Some(3),
+        Pixel::RGBA
+        | Pixel::BGRA
+        | Pixel::ARGB
+        | Pixel::ABGR
+        | Pixel::RGBZ
+        | Pixel::BGRZ
+        | Pixel::ZRGB
+        | Pixel::ZBGR => Some(4),
+        _ => None,
+    }
+}
+
+impl Video {
+    /// A `(height, width, channels)` view of the pixels, without copying.
+    ///
+    /// Only packed formats with 8-bit components, like RGB24, RGBA or GRAY8,
+    /// can be viewed, `None` is returned for other formats.
+    pub fn as_ndarray(&self) -> Option<ArrayView3<'_, u8>> {
+        let channels = channels(self.format())?;
+        let shape = (self.height() as usize, self.width() as usize, channels);
+
+        if self.planes() == 0 {
+            return None;
+        }
+
+        ArrayView3::from_shape(shape.strides((self.stride(0), channels, 1)), self.data(0)).ok()
+    }
+
+    /// A mutable `(height, width, channels)` view of the pixels, without
+    /// copying. See [`as_ndarray`][Self::as_ndarray] for the supported formats.
+    pub fn as_ndarray_mut(&mut self) -> Option<ArrayViewMut3<'_, u8>> {
+        let channels = channels(self.format())?;
+        let shape = (self.height() as usize, self.width() as usize, channels);
+
+        if self.planes() == 0 {
+            return None;
+        }
+
+        let stride = self.stride(0);
+        ArrayViewMut3::from_shape(shape.strides((stride, channels, 1)), self.data_mut(0)).ok()
+    }
+
+    /// Copies the pixels into a `(height, width, channels)` array, converting
+    /// the frame to RGB24 first if it cannot be viewed directly, which
+    /// requires the `software-scaling` feature.
+    pub fn to_ndarray(&self) -> Result<Array3<u8>, Error> {
+        if let Some(view) = self.as_ndarray() {
+            return Ok(view.to_owned());
+        }
+
+        let frame = self.converted(Pixel::RGB24)?;
+        let view = frame.as_ndarray().ok_or(Error::Bug)?;
+
+        Ok(view.to_owned())
+    }
+}
+
+/// Creates a GRAY8, RGB24 or RGBA frame from a `(height, width, channels)`
+/// array with 1, 3 or 4 channels.
+impl TryFrom<ArrayView3<'_, u8>> for Video {
+    type Error = Error;
+
+    fn try_from(array: ArrayView3<'_, u8>) -> Result<Self, Error> {
+        let (height, width, channels) = array.dim();
+
+        let format = match channels {
+            1 => Pixel::GRAY8,
+            3 => Pixel::RGB24,
+            4 => Pixel::RGBA,
+            _ => return Err(Error::Other { errno: EINVAL }),
+        };
+
+        let mut frame = Video::new(format, width as u32, height as u32);
+
+        if let Some(mut view) = frame.as_ndarray_mut() {
+            view.assign(&array);
+        }
+
+        Ok(frame)
+    }
+}
+
+impl TryFrom<&Array3<u8>> for Video {
+    type Error = Error;
+
+    fn try_from(array: &Array3<u8>) -> Result<Self, Error> {
+        Video::try_from(array.view())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn padded_view() {
+        let mut frame = Video::new(Pixel::RGB24, 5, 3);
+        let stride = frame.stride(0);
+        assert!(stride > 15);
+
+        for (index, value) in frame.data_mut(0).iter_mut().enumerate() {
+            *value = (index % 251) as u8;
+        }
+
+        let view = frame.as_ndarray().unwrap();
+        assert_eq!(view.dim(), (3, 5, 3));
+        assert_eq!(view.strides(), &[stride as isize, 3, 1]);
+        assert_eq!(view[[2, 4, 1]], ((2 * stride + 4 * 3 + 1) % 251) as u8);
+
+        let array = frame.to_ndarray().unwrap();
+        let copy = Video::try_from(&array).unwrap();
+        assert_eq!(copy.as_ndarray().unwrap(), array);
+
+        assert!(Video::new(Pixel::YUV420P, 4, 4).as_ndarray().is_none());
+    }
+}
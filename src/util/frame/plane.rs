@@ -0,0 +1,237 @@
+use std::marker::PhantomData;
+use std::mem;
+use std::slice;
+
+/// A read-only 2D view of one plane of a [`Video`][super::Video] frame.
+///
+/// Rows are `width` elements long, the padding between rows is skipped.
+pub struct PlaneView<'a, T> {
+    data: &'a [u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+    _marker: PhantomData<&'a [T]>,
+}
+
+impl<'a, T> PlaneView<'a, T> {
+    /// `data` must hold `height` rows starting `stride` bytes apart, with
+    /// `width` values of `T` at the start of each row. The last row may be
+    /// shorter than `stride`.
+    pub(crate) unsafe fn new(data: &'a [u8], width: usize, height: usize, stride: usize) -> Self {
+        PlaneView {
+            data,
+            width,
+            height,
+            stride,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Number of elements per row.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Distance between the start of two rows, in bytes.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn row(&self, y: usize) -> &'a [T] {
+        if y >= self.height {
+            panic!("out of bounds");
+        }
+
+        unsafe { row(self.data, y * self.stride, self.width) }
+    }
+
+    pub fn rows(&self) -> Rows<'a, T> {
+        Rows {
+            data: self.data,
+            width: self.width,
+            stride: self.stride,
+            remaining: self.height,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&'a T> {
+        if y < self.height {
+            self.row(y).get(x)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T> Clone for PlaneView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for PlaneView<'a, T> {}
+
+/// A mutable 2D view of one plane of a [`Video`][super::Video] frame.
+pub struct PlaneViewMut<'a, T> {
+    data: &'a mut [u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+    _marker: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T> PlaneViewMut<'a, T> {
+    /// `data` must hold `height` rows starting `stride` bytes apart, with
+    /// `width` values of `T` at the start of each row. The last row may be
+    /// shorter than `stride`.
+    pub(crate) unsafe fn new(
+        data: &'a mut [u8],
+        width: usize,
+        height: usize,
+        stride: usize,
+    ) -> Self {
+        PlaneViewMut {
+            data,
+            width,
+            height,
+            stride,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Number of elements per row.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Distance between the start of two rows, in bytes.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn as_view(&self) -> PlaneView<'_, T> {
+        unsafe { PlaneView::new(self.data, self.width, self.height, self.stride) }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        self.as_view().row(y)
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        if y >= self.height {
+            panic!("out of bounds");
+        }
+
+        unsafe { row_mut(self.data, y * self.stride, self.width) }
+    }
+
+    pub fn rows(&self) -> Rows<'_, T> {
+        self.as_view().rows()
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> + '_ {
+        let width = self.width;
+
+        self.data
+            .chunks_mut(self.stride)
+            .take(self.height)
+            .map(move |chunk| unsafe { row_mut(chunk, 0, width) })
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if y < self.height {
+            self.row(y).get(x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if y < self.height {
+            self.row_mut(y).get_mut(x)
+        } else {
+            None
+        }
+    }
+
+    /// Sets every element of the plane to `value`.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Copy,
+    {
+        for row in self.rows_mut() {
+            row.fill(value);
+        }
+    }
+}
+
+/// Iterator over the rows of a [`PlaneView`].
+pub struct Rows<'a, T> {
+    data: &'a [u8],
+    width: usize,
+    stride: usize,
+    remaining: usize,
+    _marker: PhantomData<&'a [T]>,
+}
+
+impl<'a, T> Iterator for Rows<'a, T> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<&'a [T]> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let row = unsafe { row(self.data, 0, self.width) };
+
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            self.data = &self.data[self.stride..];
+        }
+
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Rows<'a, T> {}
+
+unsafe fn row<T>(data: &[u8], offset: usize, width: usize) -> &[T] {
+    let bytes = &data[offset..offset + width * mem::size_of::<T>()];
+    slice::from_raw_parts(bytes.as_ptr() as *const T, width)
+}
+
+unsafe fn row_mut<T>(data: &mut [u8], offset: usize, width: usize) -> &mut [T] {
+    let bytes = &mut data[offset..offset + width * mem::size_of::<T>()];
+    slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut T, width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_skip_padding() {
+        let mut data = [0u8; 4 * 3];
+        let mut view = unsafe { PlaneViewMut::<[u8; 2]>::new(&mut data, 1, 3, 4) };
+
+        view.fill([1, 2]);
+        *view.get_mut(0, 2).unwrap() = [3, 4];
+
+        assert_eq!(view.rows().count(), 3);
+        assert_eq!(view.row(1), &[[1, 2]]);
+        assert_eq!(data, [1, 2, 0, 0, 1, 2, 0, 0, 3, 4, 0, 0]);
+    }
+}
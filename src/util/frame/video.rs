@@ -1,15 +1,41 @@
+#[cfg(any(feature = "image", feature = "ndarray"))]
+use std::borrow::Cow;
 use std::mem;
 use std::ops::{Deref, DerefMut};
 use std::slice;
 
-use super::Frame;
+use super::{Frame, PlaneView, PlaneViewMut};
 use crate::color;
 use crate::ffi::*;
 use crate::picture;
 use crate::util::chroma;
 use crate::util::format;
-use crate::Rational;
-use libc::c_int;
+use crate::util::image;
+use crate::{Error, Rational};
+use libc::{c_int, EINVAL};
+
+// AV_FRAME_CROP_UNALIGNED, an anonymous enum in libavutil/frame.h.
+const CROP_UNALIGNED: c_int = 1;
+
+/// A rectangle in a video frame, in pixels.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default, Hash)]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
 
 #[derive(PartialEq, Eq)]
 pub struct Video(Frame);
@@ -268,7 +294,7 @@ impl Video {
         unsafe {
             slice::from_raw_parts(
                 (*self.as_ptr()).data[index] as *const T,
-                self.stride(index) * self.plane_height(index) as usize / size_of::<T>(),
+                self.plane_len(index) / size_of::<T>(),
             )
         }
     }
@@ -286,7 +312,7 @@ impl Video {
         unsafe {
             slice::from_raw_parts_mut(
                 (*self.as_mut_ptr()).data[index] as *mut T,
-                self.stride(index) * self.plane_height(index) as usize / size_of::<T>(),
+                self.plane_len(index) / size_of::<T>(),
             )
         }
     }
@@ -297,12 +323,7 @@ impl Video {
             panic!("out of bounds");
         }
 
-        unsafe {
-            slice::from_raw_parts(
-                (*self.as_ptr()).data[index],
-                self.stride(index) * self.plane_height(index) as usize,
-            )
-        }
+        unsafe { slice::from_raw_parts((*self.as_ptr()).data[index], self.plane_len(index)) }
     }
 
     #[inline]
@@ -312,12 +333,222 @@ impl Video {
         }
 
         unsafe {
-            slice::from_raw_parts_mut(
-                (*self.as_mut_ptr()).data[index],
-                self.stride(index) * self.plane_height(index) as usize,
+            slice::from_raw_parts_mut((*self.as_mut_ptr()).data[index], self.plane_len(index))
+        }
+    }
+
+    /// A 2D view of plane `index`, whose rows hold the visible pixels only.
+    ///
+    /// `u8` can be used for any format, to access the raw bytes of the rows.
+    pub fn plane_view<T: Component>(&self, index: usize) -> PlaneView<'_, T> {
+        let width = self.row_len::<T>(index);
+
+        unsafe {
+            PlaneView::new(
+                self.data(index),
+                width,
+                self.plane_height(index) as usize,
+                self.stride(index),
             )
         }
     }
+
+    /// A mutable 2D view of plane `index`, whose rows hold the visible pixels
+    /// only.
+    pub fn plane_view_mut<T: Component>(&mut self, index: usize) -> PlaneViewMut<'_, T> {
+        let width = self.row_len::<T>(index);
+        let height = self.plane_height(index) as usize;
+        let stride = self.stride(index);
+
+        unsafe { PlaneViewMut::new(self.data_mut(index), width, height, stride) }
+    }
+
+    /// Crops the frame to `rect`, without copying the data.
+    ///
+    /// The data pointers are moved to the top left corner of `rect`, so they
+    /// may be unaligned afterwards. The slices returned by [`data`][Self::data]
+    /// and the plane accessors end with the last visible row.
+    pub fn crop(&mut self, rect: Rect) -> Result<(), Error> {
+        let right = self.width().checked_sub(rect.x.saturating_add(rect.width));
+        let bottom = self
+            .height()
+            .checked_sub(rect.y.saturating_add(rect.height));
+
+        let (Some(right), Some(bottom)) = (right, bottom) else {
+            return Err(Error::Other { errno: EINVAL });
+        };
+
+        unsafe {
+            let ptr = self.as_mut_ptr();
+
+            (*ptr).crop_left = rect.x as usize;
+            (*ptr).crop_top = rect.y as usize;
+            (*ptr).crop_right = right as usize;
+            (*ptr).crop_bottom = bottom as usize;
+
+            match av_frame_apply_cropping(ptr, CROP_UNALIGNED) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Copies the data of `source`, which must have the same format and
+    /// dimensions, into the buffers of this frame.
+    pub fn copy_from(&mut self, source: &Video) -> Result<(), Error> {
+        unsafe {
+            match av_frame_copy(self.as_mut_ptr(), source.as_ptr()) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Fills the whole frame with `color`, given as the values of the
+    /// components of the format in their native depth, e.g. `[y, u, v, a]`
+    /// or `[r, g, b, a]`.
+    #[cfg(feature = "ffmpeg_6_1")]
+    pub fn fill(&mut self, color: [u32; 4]) -> Result<(), Error> {
+        unsafe {
+            let (mut data, linesize) = self.pointers();
+
+            match av_image_fill_color(
+                data.as_mut_ptr(),
+                linesize.as_ptr(),
+                self.format().into(),
+                color.as_ptr(),
+                self.width() as c_int,
+                self.height() as c_int,
+                0,
+            ) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Fills the whole frame with black, respecting the color range.
+    pub fn fill_black(&mut self) -> Result<(), Error> {
+        unsafe {
+            let (mut data, linesize) = self.pointers();
+
+            match av_image_fill_black(
+                data.as_mut_ptr(),
+                linesize.as_ptr(),
+                self.format().into(),
+                self.color_range().into(),
+                self.width() as c_int,
+                self.height() as c_int,
+            ) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Copies the pixels into a single buffer, planes one after the other,
+    /// with rows aligned to `align` bytes. Use an alignment of 1 for tightly
+    /// packed rows.
+    pub fn to_packed_bytes(&self, align: u32) -> Result<Vec<u8>, Error> {
+        let size = image::buffer_size(self.format(), self.width(), self.height(), align)?;
+        let mut buffer = vec![0; size];
+
+        image::copy_to_buffer(self, &mut buffer, align)?;
+
+        Ok(buffer)
+    }
+
+    /// Creates a frame from pixels stored as written by
+    /// [`to_packed_bytes`][Self::to_packed_bytes] with the same alignment.
+    pub fn from_packed_bytes(
+        format: format::Pixel,
+        width: u32,
+        height: u32,
+        bytes: &[u8],
+        align: u32,
+    ) -> Result<Self, Error> {
+        if format == format::Pixel::None || width == 0 || height == 0 {
+            return Err(Error::Other { errno: EINVAL });
+        }
+
+        let mut frame = Video::new(format, width, height);
+        image::copy_from_buffer(&mut frame, bytes, align)?;
+
+        Ok(frame)
+    }
+
+    // This frame if it already has `format`, a converted copy otherwise.
+    #[cfg(any(feature = "image", feature = "ndarray"))]
+    pub(super) fn converted(&self, format: format::Pixel) -> Result<Cow<'_, Video>, Error> {
+        if self.format() == format {
+            return Ok(Cow::Borrowed(self));
+        }
+
+        #[cfg(feature = "software-scaling")]
+        {
+            let mut output = Video::empty();
+            self.converter(format)?.run(self, &mut output)?;
+
+            Ok(Cow::Owned(output))
+        }
+
+        #[cfg(not(feature = "software-scaling"))]
+        Err(Error::Other { errno: EINVAL })
+    }
+
+    // Number of values of `T` holding the visible pixels of a row.
+    // Number of bytes from the start of plane `index` to the end of its last
+    // visible row. After cropping the last row may not be followed by padding,
+    // so this is less than `stride * plane_height`.
+    fn plane_len(&self, index: usize) -> usize {
+        let height = self.plane_height(index) as usize;
+        let stride = self.stride(index);
+
+        if height == 0 {
+            return 0;
+        }
+
+        let bytes = unsafe {
+            av_image_get_linesize(self.format().into(), self.width() as c_int, index as c_int)
+        };
+
+        let row = if bytes < 0 {
+            stride
+        } else {
+            (bytes as usize).min(stride)
+        };
+
+        stride * (height - 1) + row
+    }
+
+    fn row_len<T: Component>(&self, index: usize) -> usize {
+        if index >= self.planes() {
+            panic!("out of bounds");
+        }
+
+        if !<T as Component>::is_valid(self.format()) {
+            panic!("unsupported type");
+        }
+
+        let bytes = unsafe {
+            av_image_get_linesize(self.format().into(), self.width() as c_int, index as c_int)
+        };
+
+        if bytes < 0 {
+            panic!("unsupported format");
+        }
+
+        bytes as usize / size_of::<T>()
+    }
+
+    unsafe fn pointers(&mut self) -> ([*mut u8; 4], [isize; 4]) {
+        let ptr = self.as_mut_ptr();
+
+        (
+            std::array::from_fn(|i| (*ptr).data[i]),
+            std::array::from_fn(|i| (*ptr).linesize[i] as isize),
+        )
+    }
 }
 
 impl Deref for Video {
@@ -361,10 +592,25 @@ impl From<Frame> for Video {
     }
 }
 
+impl From<Video> for Frame {
+    #[inline]
+    fn from(frame: Video) -> Self {
+        frame.0
+    }
+}
+
 pub unsafe trait Component {
     fn is_valid(format: format::Pixel) -> bool;
 }
 
+// Raw bytes, valid for any format.
+unsafe impl Component for u8 {
+    #[inline(always)]
+    fn is_valid(_format: format::Pixel) -> bool {
+        true
+    }
+}
+
 #[cfg(feature = "image")]
 unsafe impl Component for ::image::Luma<u8> {
     #[inline(always)]
@@ -430,3 +676,30 @@ unsafe impl Component for (u8, u8, u8, u8) {
             || format == format::Pixel::ZBGR
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crop_last_row() {
+        let mut frame = Video::new(format::Pixel::RGB24, 8, 4);
+        let stride = frame.stride(0);
+
+        for (y, row) in frame.plane_view_mut::<u8>(0).rows_mut().enumerate() {
+            for (x, value) in row.iter_mut().enumerate() {
+                *value = (y * 24 + x) as u8;
+            }
+        }
+
+        frame.crop(Rect::new(2, 1, 6, 3)).unwrap();
+        assert_eq!(frame.data(0).len(), 2 * stride + 18);
+
+        let view = frame.plane_view::<u8>(0);
+        let last = view.rows().last().unwrap();
+        assert_eq!(last.len(), 18);
+        assert_eq!(last[0], 3 * 24 + 6);
+        assert_eq!(last[17], 3 * 24 + 23);
+        assert_eq!(frame.data(0)[2 * stride + 17], 3 * 24 + 23);
+    }
+}
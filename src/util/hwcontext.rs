@@ -0,0 +1,92 @@
+use crate::ffi::*;
+use crate::utils;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Type of a hardware device, i.e. the API used to access it.
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum DeviceType {
+    None,
+    VDPAU,
+    CUDA,
+    VAAPI,
+    DXVA2,
+    QSV,
+    VideoToolbox,
+    D3D11VA,
+    DRM,
+    OpenCL,
+    MediaCodec,
+    Vulkan,
+    #[cfg(feature = "ffmpeg_7_0")]
+    D3D12VA,
+    #[cfg(feature = "ffmpeg_8_0")]
+    AMF,
+    /// OpenHarmony codec device.
+    #[cfg(feature = "ffmpeg_8_0")]
+    OHCodec,
+}
+
+impl DeviceType {
+    /// Name of the device type as used on the command line, e.g. `vaapi`.
+    pub fn name(&self) -> Option<&'static str> {
+        unsafe { utils::optional_str_from_c_ptr(av_hwdevice_get_type_name((*self).into())) }
+    }
+}
+
+impl From<AVHWDeviceType> for DeviceType {
+    fn from(value: AVHWDeviceType) -> Self {
+        use AVHWDeviceType as AV;
+
+        match value {
+            AV::NONE => DeviceType::None,
+            AV::VDPAU => DeviceType::VDPAU,
+            AV::CUDA => DeviceType::CUDA,
+            AV::VAAPI => DeviceType::VAAPI,
+            AV::DXVA2 => DeviceType::DXVA2,
+            AV::QSV => DeviceType::QSV,
+            AV::VIDEOTOOLBOX => DeviceType::VideoToolbox,
+            AV::D3D11VA => DeviceType::D3D11VA,
+            AV::DRM => DeviceType::DRM,
+            AV::OPENCL => DeviceType::OpenCL,
+            AV::MEDIACODEC => DeviceType::MediaCodec,
+            AV::VULKAN => DeviceType::Vulkan,
+            #[cfg(feature = "ffmpeg_7_0")]
+            AV::D3D12VA => DeviceType::D3D12VA,
+            #[cfg(feature = "ffmpeg_8_0")]
+            AV::AMF => DeviceType::AMF,
+            #[cfg(feature = "ffmpeg_8_0")]
+            AV::OHCODEC => DeviceType::OHCodec,
+
+            _ => unimplemented!(),
+        }
+    }
+}
+
+impl From<DeviceType> for AVHWDeviceType {
+    fn from(value: DeviceType) -> AVHWDeviceType {
+        use AVHWDeviceType as AV;
+
+        match value {
+            DeviceType::None => AV::NONE,
+            DeviceType::VDPAU => AV::VDPAU,
+            DeviceType::CUDA => AV::CUDA,
+            DeviceType::VAAPI => AV::VAAPI,
+            DeviceType::DXVA2 => AV::DXVA2,
+            DeviceType::QSV => AV::QSV,
+            DeviceType::VideoToolbox => AV::VIDEOTOOLBOX,
+            DeviceType::D3D11VA => AV::D3D11VA,
+            DeviceType::DRM => AV::DRM,
+            DeviceType::OpenCL => AV::OPENCL,
+            DeviceType::MediaCodec => AV::MEDIACODEC,
+            DeviceType::Vulkan => AV::VULKAN,
+            #[cfg(feature = "ffmpeg_7_0")]
+            DeviceType::D3D12VA => AV::D3D12VA,
+            #[cfg(feature = "ffmpeg_8_0")]
+            DeviceType::AMF => AV::AMF,
+            #[cfg(feature = "ffmpeg_8_0")]
+            DeviceType::OHCodec => AV::OHCODEC,
+        }
+    }
+}
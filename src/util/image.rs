@@ -0,0 +1,214 @@
+//! Size and layout of images stored in flat buffers.
+
+use std::ptr;
+
+use crate::ffi::*;
+use crate::util::format;
+use crate::{frame, Error};
+use libc::{c_int, EINVAL};
+
+/// Location of the planes of an image in a flat buffer.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default)]
+pub struct Layout {
+    /// Offset of the start of every plane, in bytes.
+    pub offsets: [usize; 4],
+    /// Size of a row of every plane, in bytes.
+    pub linesizes: [usize; 4],
+    /// Total size of the image, in bytes.
+    pub size: usize,
+}
+
+impl Layout {
+    /// Number of planes, including the palette of paletted formats.
+    pub fn planes(&self) -> usize {
+        if self.size == 0 {
+            return 0;
+        }
+
+        1 + self.offsets[1..]
+            .iter()
+            .take_while(|&&offset| offset > 0)
+            .count()
+    }
+}
+
+/// Size in bytes of an image, with rows aligned to `align` bytes.
+pub fn buffer_size(
+    format: format::Pixel,
+    width: u32,
+    height: u32,
+    align: u32,
+) -> Result<usize, Error> {
+    unsafe {
+        match av_image_get_buffer_size(
+            format.into(),
+            width as c_int,
+            height as c_int,
+            align as c_int,
+        ) {
+            e if e < 0 => Err(Error::from(e)),
+            size => Ok(size as usize),
+        }
+    }
+}
+
+/// Size in bytes of a row of every plane, without padding.
+pub fn fill_linesizes(format: format::Pixel, width: u32) -> Result<[usize; 4], Error> {
+    unsafe {
+        let mut linesizes = [0; 4];
+
+        match av_image_fill_linesizes(linesizes.as_mut_ptr(), format.into(), width as c_int) {
+            e if e < 0 => Err(Error::from(e)),
+            _ => Ok(linesizes.map(|size| size as usize)),
+        }
+    }
+}
+
+/// Layout of an image stored in `buffer`, with rows aligned to `align`
+/// bytes.
+pub fn fill_arrays(
+    buffer: &[u8],
+    format: format::Pixel,
+    width: u32,
+    height: u32,
+    align: u32,
+) -> Result<Layout, Error> {
+    if buffer.len() < buffer_size(format, width, height, align)? {
+        return Err(Error::BufferTooSmall);
+    }
+
+    unsafe {
+        let mut data = [ptr::null_mut(); 4];
+        let mut linesizes = [0; 4];
+
+        let size = av_image_fill_arrays(
+            data.as_mut_ptr(),
+            linesizes.as_mut_ptr(),
+            buffer.as_ptr(),
+            format.into(),
+            width as c_int,
+            height as c_int,
+            align as c_int,
+        );
+
+        if size < 0 {
+            return Err(Error::from(size));
+        }
+
+        let mut layout = Layout {
+            size: size as usize,
+            ..Layout::default()
+        };
+
+        for (i, (plane, &linesize)) in data.iter().zip(&linesizes).enumerate() {
+            if !plane.is_null() {
+                layout.offsets[i] = plane.offset_from(buffer.as_ptr()) as usize;
+                layout.linesizes[i] = linesize as usize;
+            }
+        }
+
+        Ok(layout)
+    }
+}
+
+/// Copies the pixels of `frame` into `buffer`, with rows aligned to `align`
+/// bytes. Returns the number of bytes written, or `EINVAL` if `frame` has no
+/// data.
+pub fn copy_to_buffer(frame: &frame::Video, buffer: &mut [u8], align: u32) -> Result<usize, Error> {
+    if unsafe { (*frame.as_ptr()).data[0].is_null() } {
+        return Err(Error::Other { errno: EINVAL });
+    }
+
+    unsafe {
+        let ptr = frame.as_ptr();
+
+        match av_image_copy_to_buffer(
+            buffer.as_mut_ptr(),
+            buffer.len().min(c_int::MAX as usize) as c_int,
+            (*ptr).data.as_ptr() as *const *const u8,
+            (*ptr).linesize.as_ptr(),
+            frame.format().into(),
+            frame.width() as c_int,
+            frame.height() as c_int,
+            align as c_int,
+        ) {
+            e if e < 0 => Err(Error::from(e)),
+            size => Ok(size as usize),
+        }
+    }
+}
+
+/// Copies an image stored in `buffer`, with rows aligned to `align` bytes,
+/// into `frame`, which must be allocated with the same format and size.
+pub fn copy_from_buffer(frame: &mut frame::Video, buffer: &[u8], align: u32) -> Result<(), Error> {
+    let (format, width, height) = (frame.format(), frame.width(), frame.height());
+
+    if unsafe { (*frame.as_ptr()).data[0].is_null() } {
+        return Err(Error::Other { errno: EINVAL });
+    }
+
+    let layout = fill_arrays(buffer, format, width, height, align)?;
+
+    unsafe {
+        let planes = layout.planes();
+        let data: [*const u8; 4] = std::array::from_fn(|i| {
+            if i < planes {
+                buffer.as_ptr().add(layout.offsets[i])
+            } else {
+                ptr::null()
+            }
+        });
+        let linesizes = layout.linesizes.map(|size| size as c_int);
+
+        let ptr = frame.as_mut_ptr();
+
+        av_image_copy(
+            (*ptr).data.as_mut_ptr(),
+            (*ptr).linesize.as_mut_ptr(),
+            data.as_ptr() as *mut *const u8,
+            linesizes.as_ptr(),
+            format.into(),
+            width as c_int,
+            height as c_int,
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packed_round_trip() {
+        for format in [
+            format::Pixel::NV12,
+            format::Pixel::YUV420P,
+            format::Pixel::PAL8,
+        ] {
+            let mut frame = frame::Video::new(format, 5, 3);
+            frame.data_mut(0).fill(7);
+
+            let bytes = frame.to_packed_bytes(1).unwrap();
+            assert_eq!(bytes.len(), buffer_size(format, 5, 3, 1).unwrap());
+
+            let copy = frame::Video::from_packed_bytes(format, 5, 3, &bytes, 1).unwrap();
+            assert_eq!(copy.to_packed_bytes(1).unwrap(), bytes);
+        }
+
+        assert_eq!(
+            fill_arrays(&[0; 8], format::Pixel::NV12, 4, 4, 1),
+            Err(Error::BufferTooSmall)
+        );
+
+        let mut empty = frame::Video::empty();
+        empty.set_format(format::Pixel::GRAY8);
+        empty.set_width(4);
+        empty.set_height(4);
+        assert_eq!(
+            copy_to_buffer(&empty, &mut [0; 16], 1),
+            Err(Error::Other { errno: EINVAL })
+        );
+    }
+}
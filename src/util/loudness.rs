@@ -0,0 +1,277 @@
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+
+use crate::{frame, Channel, ChannelLayout, Error};
+use libc::EINVAL;
+
+// Sub-blocks of 100ms, momentary and short-term loudness are measured over
+// the last 4 and 30 of them.
+const MOMENTARY_BLOCKS: usize = 4;
+const SHORT_TERM_BLOCKS: usize = 30;
+
+const ABSOLUTE_GATE: f64 = -70.0;
+const RELATIVE_GATE: f64 = -10.0;
+
+/// Loudness meter following ITU-R BS.1770 / EBU R 128.
+///
+/// Momentary, short-term and integrated loudness are returned in LUFS, or
+/// `None` until enough audio has been measured.
+#[derive(Clone, Debug)]
+pub struct LoudnessMeter {
+    rate: u32,
+    weights: Vec<f64>,
+    filters: Vec<KWeighting>,
+
+    block_size: usize,
+    block_samples: usize,
+    block_energy: f64,
+
+    blocks: VecDeque<f64>,
+    history: Vec<f64>,
+    peak: f64,
+}
+
+impl LoudnessMeter {
+    /// Creates a meter for frames with the given channel layout and rate.
+    ///
+    /// LFE channels are ignored and surround channels are weighted by +1.5dB.
+    pub fn new(layout: &ChannelLayout, rate: u32) -> Result<Self, Error> {
+        let weights = (0..layout.channels())
+            .map(|index| match layout.channel_from_index(index) {
+                Channel::LowFrequency | Channel::LowFrequency2 => 0.0,
+
+                Channel::BackLeft
+                | Channel::BackRight
+                | Channel::SideLeft
+                | Channel::SideRight
+                | Channel::SurroundDirectLeft
+                | Channel::SurroundDirectRight => 1.41,
+
+                _ => 1.0,
+            })
+            .collect();
+
+        Self::with_weights(weights, rate)
+    }
+
+    /// Creates a meter with an explicit weight for every channel.
+    pub fn with_weights(weights: Vec<f64>, rate: u32) -> Result<Self, Error> {
+        if weights.is_empty() || rate < 10 {
+            return Err(Error::Other { errno: EINVAL });
+        }
+
+        Ok(LoudnessMeter {
+            rate,
+            filters: vec![KWeighting::new(rate); weights.len()],
+            weights,
+
+            block_size: rate as usize / 10,
+            block_samples: 0,
+            block_energy: 0.0,
+
+            blocks: VecDeque::with_capacity(SHORT_TERM_BLOCKS),
+            history: Vec::new(),
+            peak: 0.0,
+        })
+    }
+
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    pub fn channels(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Measures all samples of `frame`, which may have any sample format.
+    pub fn add(&mut self, frame: &frame::Audio) -> Result<(), Error> {
+        if frame.rate() != self.rate || frame.ch_layout().channels() as usize != self.channels() {
+            return Err(Error::InputChanged);
+        }
+
+        self.process(&frame.interleaved::<f64>());
+
+        Ok(())
+    }
+
+    /// Loudness of the last 400ms.
+    pub fn momentary(&self) -> Option<f64> {
+        self.window(MOMENTARY_BLOCKS)
+    }
+
+    /// Loudness of the last 3s.
+    pub fn short_term(&self) -> Option<f64> {
+        self.window(SHORT_TERM_BLOCKS)
+    }
+
+    /// Gated loudness of everything measured so far.
+    pub fn integrated(&self) -> Option<f64> {
+        let absolute = gated_mean(&self.history, ABSOLUTE_GATE)?;
+        let relative = loudness(absolute) + RELATIVE_GATE;
+
+        gated_mean(&self.history, relative.max(ABSOLUTE_GATE)).map(loudness)
+    }
+
+    /// Highest absolute sample value seen so far, where 1.0 is full scale.
+    pub fn peak(&self) -> f64 {
+        self.peak
+    }
+
+    /// Forgets everything measured so far.
+    pub fn reset(&mut self) {
+        *self = Self::with_weights(self.weights.clone(), self.rate).unwrap();
+    }
+
+    fn process(&mut self, samples: &[f64]) {
+        for frame in samples.chunks_exact(self.channels()) {
+            for (channel, &sample) in frame.iter().enumerate() {
+                self.peak = self.peak.max(sample.abs());
+
+                let filtered = self.filters[channel].process(sample);
+                self.block_energy += self.weights[channel] * filtered * filtered;
+            }
+
+            self.block_samples += 1;
+
+            if self.block_samples == self.block_size {
+                self.end_block();
+            }
+        }
+    }
+
+    fn end_block(&mut self) {
+        if self.blocks.len() == SHORT_TERM_BLOCKS {
+            self.blocks.pop_back();
+        }
+
+        self.blocks
+            .push_front(self.block_energy / self.block_size as f64);
+        self.block_samples = 0;
+        self.block_energy = 0.0;
+
+        // Gating blocks of 400ms overlap by 75%.
+        if self.blocks.len() >= MOMENTARY_BLOCKS {
+            let energy = self.blocks.iter().take(MOMENTARY_BLOCKS).sum::<f64>();
+            self.history.push(energy / MOMENTARY_BLOCKS as f64);
+        }
+    }
+
+    fn window(&self, blocks: usize) -> Option<f64> {
+        if self.blocks.len() < blocks {
+            return None;
+        }
+
+        let energy = self.blocks.iter().take(blocks).sum::<f64>();
+        Some(loudness(energy / blocks as f64))
+    }
+}
+
+fn loudness(energy: f64) -> f64 {
+    -0.691 + 10.0 * energy.log10()
+}
+
+fn gated_mean(energies: &[f64], gate: f64) -> Option<f64> {
+    let (sum, count) = energies
+        .iter()
+        .filter(|&&energy| loudness(energy) > gate)
+        .fold((0.0, 0usize), |(sum, count), energy| {
+            (sum + energy, count + 1)
+        });
+
+    (count > 0).then(|| sum / count as f64)
+}
+
+#[derive(Clone, Debug)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    z: [f64; 2],
+}
+
+impl Biquad {
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.z[0];
+        self.z[0] = self.b[1] * x - self.a[0] * y + self.z[1];
+        self.z[1] = self.b[2] * x - self.a[1] * y;
+        y
+    }
+}
+
+// The two stage K-weighting pre-filter, computed for any sample rate.
+#[derive(Clone, Debug)]
+struct KWeighting {
+    shelf: Biquad,
+    high_pass: Biquad,
+}
+
+impl KWeighting {
+    fn new(rate: u32) -> Self {
+        let rate = f64::from(rate);
+
+        let f0 = 1681.974450955533;
+        let gain = 3.999843853973347;
+        let q = 0.7071752369554196;
+
+        let k = (PI * f0 / rate).tan();
+        let vh = 10f64.powf(gain / 20.0);
+        let vb = vh.powf(0.4996667741545416);
+        let a0 = 1.0 + k / q + k * k;
+
+        let shelf = Biquad {
+            b: [
+                (vh + vb * k / q + k * k) / a0,
+                2.0 * (k * k - vh) / a0,
+                (vh - vb * k / q + k * k) / a0,
+            ],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+            z: [0.0; 2],
+        };
+
+        let f0 = 38.13547087602444;
+        let q = 0.5003270373238773;
+
+        let k = (PI * f0 / rate).tan();
+        let a0 = 1.0 + k / q + k * k;
+
+        let high_pass = Biquad {
+            b: [1.0, -2.0, 1.0],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+            z: [0.0; 2],
+        };
+
+        KWeighting { shelf, high_pass }
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        self.high_pass.process(self.shelf.process(x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sine_loudness() {
+        // EBU Tech 3341: a stereo 1kHz sine at -23dBFS measures -23 LUFS.
+        let rate = 48000;
+        let amplitude = 10f64.powf(-23.0 / 20.0);
+
+        let mut meter = LoudnessMeter::with_weights(vec![1.0, 1.0], rate).unwrap();
+        assert_eq!(meter.integrated(), None);
+
+        let samples: Vec<f64> = (0..rate as usize * 5)
+            .flat_map(|i| {
+                let value = amplitude * (2.0 * PI * 997.0 * i as f64 / rate as f64).sin();
+                [value, value]
+            })
+            .collect();
+
+        meter.process(&samples);
+
+        assert!((meter.integrated().unwrap() + 23.0).abs() < 0.1);
+        assert!((meter.momentary().unwrap() + 23.0).abs() < 0.1);
+        assert!((meter.short_term().unwrap() + 23.0).abs() < 0.1);
+        assert!((meter.peak() - amplitude).abs() < 1e-3);
+    }
+}
@@ -3,3 +3,6 @@ pub use self::rounding::Rounding;
 
 pub mod rescale;
 pub use self::rescale::Rescale;
+
+pub mod timestamp;
+pub use self::timestamp::Timestamp;
@@ -0,0 +1,148 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::time::Duration;
+
+use super::Rounding;
+use crate::ffi::*;
+use crate::Rational;
+
+const NANOSECONDS: Rational = Rational(1, 1_000_000_000);
+const MILLISECONDS: Rational = Rational(1, 1_000);
+
+/// A timestamp together with the time base it is expressed in.
+///
+/// Timestamps in different time bases compare by the instant they represent,
+/// e.g. `1/25` and `40/1000` are equal.
+#[derive(Copy, Clone, Debug)]
+pub struct Timestamp {
+    pub value: i64,
+    pub time_base: Rational,
+}
+
+impl Timestamp {
+    #[inline]
+    pub fn new<T: Into<Rational>>(value: i64, time_base: T) -> Self {
+        Timestamp {
+            value,
+            time_base: time_base.into(),
+        }
+    }
+
+    /// Converts `duration` to a timestamp in `time_base`, saturating at
+    /// `i64::MAX` nanoseconds.
+    pub fn from_duration<T: Into<Rational>>(duration: Duration, time_base: T) -> Self {
+        let nanos = i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX);
+        Timestamp::new(nanos, NANOSECONDS).rescale_to(time_base, Rounding::NearInfinity)
+    }
+
+    /// The same instant in `time_base`.
+    #[inline]
+    pub fn rescale_to<T: Into<Rational>>(&self, time_base: T, rounding: Rounding) -> Self {
+        let time_base = time_base.into();
+
+        unsafe {
+            Timestamp {
+                value: av_rescale_q_rnd(
+                    self.value,
+                    self.time_base.into(),
+                    time_base.into(),
+                    rounding.into(),
+                ),
+                time_base,
+            }
+        }
+    }
+
+    #[inline]
+    pub fn seconds(&self) -> f64 {
+        self.value as f64 * f64::from(self.time_base)
+    }
+
+    /// The time elapsed since zero, or `None` if the timestamp is negative.
+    pub fn to_duration(&self) -> Option<Duration> {
+        let nanos = self.rescale_to(NANOSECONDS, Rounding::NearInfinity).value;
+        u64::try_from(nanos).ok().map(Duration::from_nanos)
+    }
+}
+
+impl PartialEq for Timestamp {
+    fn eq(&self, other: &Timestamp) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Timestamp {}
+
+impl PartialOrd for Timestamp {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Timestamp {
+    fn cmp(&self, other: &Self) -> Ordering {
+        unsafe {
+            av_compare_ts(
+                self.value,
+                self.time_base.into(),
+                other.value,
+                other.time_base.into(),
+            )
+            .cmp(&0)
+        }
+    }
+}
+
+/// Formats the timestamp as `HH:MM:SS.mmm`, or as seconds with the
+/// alternate flag, e.g. `{:#.3}`.
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            let precision = f.precision().unwrap_or(6);
+            return write!(f, "{:.*}", precision, self.seconds());
+        }
+
+        let millis = self.rescale_to(MILLISECONDS, Rounding::NearInfinity).value;
+        let sign = if millis < 0 { "-" } else { "" };
+        let millis = millis.unsigned_abs();
+
+        write!(
+            f,
+            "{}{:02}:{:02}:{:02}.{:03}",
+            sign,
+            millis / 3_600_000,
+            millis / 60_000 % 60,
+            millis / 1_000 % 60,
+            millis % 1_000
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_and_format() {
+        let frame = Timestamp::new(1, (1, 25));
+        let millis = Timestamp::new(40, (1, 1000));
+
+        assert_eq!(frame, millis);
+        assert!(Timestamp::new(2, (1, 25)) > millis);
+        assert_eq!(frame.rescale_to((1, 90000), Rounding::Zero).value, 3600);
+
+        let ts = Timestamp::new(3_723_456, (1, 1000));
+        assert_eq!(ts.to_string(), "01:02:03.456");
+        assert_eq!(format!("{:#.1}", ts), "3723.5");
+        assert_eq!(
+            Timestamp::new(-1500, (1, 1000)).to_string(),
+            "-00:00:01.500"
+        );
+
+        let duration = Duration::from_millis(1500);
+        assert_eq!(ts.to_duration(), Some(Duration::from_millis(3_723_456)));
+        assert_eq!(Timestamp::from_duration(duration, (1, 10)).value, 15);
+        assert_eq!(Timestamp::new(-1, (1, 10)).to_duration(), None);
+    }
+}
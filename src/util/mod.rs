@@ -1,3 +1,4 @@
+pub mod audio_fifo;
 pub mod channel_layout;
 pub mod chroma;
 pub mod color;
@@ -5,8 +6,11 @@ pub mod dictionary;
 pub mod error;
 pub mod format;
 pub mod frame;
+pub mod hwcontext;
+pub mod image;
 pub mod interrupt;
 pub mod log;
+pub mod loudness;
 pub mod mathematics;
 pub mod media;
 pub mod option;
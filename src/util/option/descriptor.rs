@@ -0,0 +1,388 @@
+use std::fmt;
+use std::iter;
+use std::marker::PhantomData;
+use std::ptr;
+
+use super::Type;
+use crate::ffi::*;
+use crate::{utils, Rational};
+use libc::{c_int, c_void};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+    pub struct Flags: c_int {
+        const ENCODING_PARAM  = AV_OPT_FLAG_ENCODING_PARAM as c_int;
+        const DECODING_PARAM  = AV_OPT_FLAG_DECODING_PARAM as c_int;
+        const AUDIO_PARAM     = AV_OPT_FLAG_AUDIO_PARAM as c_int;
+        const VIDEO_PARAM     = AV_OPT_FLAG_VIDEO_PARAM as c_int;
+        const SUBTITLE_PARAM  = AV_OPT_FLAG_SUBTITLE_PARAM as c_int;
+        /// The option is for exporting values to the caller.
+        const EXPORT          = AV_OPT_FLAG_EXPORT as c_int;
+        /// The option may not be set through the AVOptions API.
+        const READONLY        = AV_OPT_FLAG_READONLY as c_int;
+        const BSF_PARAM       = AV_OPT_FLAG_BSF_PARAM as c_int;
+        /// The option can be set at runtime.
+        const RUNTIME_PARAM   = AV_OPT_FLAG_RUNTIME_PARAM as c_int;
+        const FILTERING_PARAM = AV_OPT_FLAG_FILTERING_PARAM as c_int;
+        const DEPRECATED      = AV_OPT_FLAG_DEPRECATED as c_int;
+        /// The named constants of the option are in the child classes.
+        const CHILD_CONSTS    = AV_OPT_FLAG_CHILD_CONSTS as c_int;
+    }
+}
+
+/// Default value of an option.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Value<'a> {
+    None,
+    Int(i64),
+    Double(f64),
+    Rational(Rational),
+    Str(&'a str),
+}
+
+/// An `AVClass`, describing the options of a kind of object.
+#[derive(Copy, Clone)]
+pub struct Class<'a> {
+    ptr: *const AVClass,
+    _marker: PhantomData<&'a AVClass>,
+}
+
+impl<'a> Class<'a> {
+    /// Returns `None` if `ptr` is null.
+    pub unsafe fn from_raw(ptr: *const AVClass) -> Option<Self> {
+        if ptr.is_null() {
+            None
+        } else {
+            Some(Class {
+                ptr,
+                _marker: PhantomData,
+            })
+        }
+    }
+
+    pub fn as_ptr(&self) -> *const AVClass {
+        self.ptr
+    }
+
+    pub fn name(&self) -> &'a str {
+        unsafe { utils::str_from_c_ptr((*self.ptr).class_name) }
+    }
+
+    /// Options of this class, without the ones of its child classes.
+    pub fn options(&self) -> Options<'a> {
+        Options::for_class(self.ptr)
+    }
+
+    /// Looks up an option by name, without searching the child classes.
+    pub fn find(&self, name: &str) -> Option<Descriptor<'a>> {
+        self.options().find(|option| option.name() == name)
+    }
+
+    /// Classes of the objects that may be children of objects of this class.
+    pub fn children(&self) -> ChildClasses<'a> {
+        ChildClasses {
+            class: self.ptr,
+            state: ptr::null_mut(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl fmt::Debug for Class<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Class").field(&self.name()).finish()
+    }
+}
+
+pub struct ChildClasses<'a> {
+    class: *const AVClass,
+    state: *mut c_void,
+    _marker: PhantomData<&'a AVClass>,
+}
+
+impl<'a> Iterator for ChildClasses<'a> {
+    type Item = Class<'a>;
+
+    fn next(&mut self) -> Option<Class<'a>> {
+        unsafe {
+            let iterate = (*self.class).child_class_iterate?;
+            Class::from_raw(iterate(&mut self.state))
+        }
+    }
+}
+
+/// Description of a single option.
+#[derive(Copy, Clone)]
+pub struct Descriptor<'a> {
+    option: &'a AVOption,
+    class: *const AVClass,
+    // The object whose options are iterated, null when iterating a class.
+    object: *const c_void,
+}
+
+impl<'a> Descriptor<'a> {
+    pub fn as_ptr(&self) -> *const AVOption {
+        self.option
+    }
+
+    /// The class this option belongs to.
+    pub fn class(&self) -> Class<'a> {
+        Class {
+            ptr: self.class,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &'a str {
+        unsafe { utils::str_from_c_ptr(self.option.name) }
+    }
+
+    pub fn help(&self) -> Option<&'a str> {
+        unsafe { utils::optional_str_from_c_ptr(self.option.help) }
+    }
+
+    pub fn kind(&self) -> Type {
+        Type::from(self.option.type_)
+    }
+
+    pub fn flags(&self) -> Flags {
+        Flags::from_bits_truncate(self.option.flags)
+    }
+
+    pub fn min(&self) -> f64 {
+        self.option.min
+    }
+
+    pub fn max(&self) -> f64 {
+        self.option.max
+    }
+
+    /// Name of the group of named constants accepted by this option.
+    pub fn unit(&self) -> Option<&'a str> {
+        unsafe { utils::optional_str_from_c_ptr(self.option.unit) }
+    }
+
+    pub fn default(&self) -> Value<'a> {
+        use AVOptionType as AV;
+
+        unsafe {
+            let value = &self.option.default_val;
+
+            #[cfg(feature = "ffmpeg_7_0")]
+            if self.kind().contains(Type::FLAG_ARRAY) {
+                return match value.arr.as_ref() {
+                    Some(array) => {
+                        utils::optional_str_from_c_ptr(array.def).map_or(Value::None, Value::Str)
+                    }
+                    None => Value::None,
+                };
+            }
+
+            match self.option.type_ {
+                AV::FLAGS
+                | AV::INT
+                | AV::INT64
+                | AV::UINT64
+                | AV::CONST
+                | AV::PIXEL_FMT
+                | AV::SAMPLE_FMT
+                | AV::DURATION
+                | AV::BOOL => Value::Int(value.i64_),
+
+                #[cfg(not(feature = "ffmpeg_7_0"))]
+                AV::CHANNEL_LAYOUT => Value::Int(value.i64_),
+
+                AV::DOUBLE | AV::FLOAT => Value::Double(value.dbl),
+
+                // Rational defaults are stored as doubles.
+                AV::RATIONAL => Value::Rational(Rational::from(av_d2q(value.dbl, c_int::MAX))),
+
+                AV::STRING
+                | AV::BINARY
+                | AV::DICT
+                | AV::IMAGE_SIZE
+                | AV::VIDEO_RATE
+                | AV::COLOR
+                | AV::CHLAYOUT => {
+                    utils::optional_str_from_c_ptr(value.str_).map_or(Value::None, Value::Str)
+                }
+
+                _ => Value::None,
+            }
+        }
+    }
+
+    /// Whether this is a named constant, a possible value of the options
+    /// with the same unit.
+    pub fn is_constant(&self) -> bool {
+        self.option.type_ == AVOptionType::CONST
+    }
+
+    /// Named constants accepted by this option, e.g. the presets of an
+    /// enumeration or the bits of a flags option.
+    ///
+    /// With [`Flags::CHILD_CONSTS`], the constants are also looked up in the
+    /// child objects, e.g. the codec private options for the `profile` of a
+    /// codec context. For options of a [`Class`], the constants of all its
+    /// [child classes][Class::children] are returned.
+    pub fn constants(&self) -> Vec<Descriptor<'a>> {
+        let Some(unit) = self.unit() else {
+            return Vec::new();
+        };
+
+        if self.is_constant() {
+            return Vec::new();
+        }
+
+        let is_match = |option: &Descriptor| option.is_constant() && option.unit() == Some(unit);
+
+        if !self.flags().contains(Flags::CHILD_CONSTS) {
+            return Options::for_class(self.class)
+                .with_constants()
+                .filter(is_match)
+                .collect();
+        }
+
+        if !self.object.is_null() {
+            return unsafe { Options::for_object(self.object) }
+                .with_constants()
+                .filter(is_match)
+                .collect();
+        }
+
+        iter::once(self.class())
+            .chain(self.class().children())
+            .flat_map(|class| Options::for_class(class.as_ptr()).with_constants())
+            .filter(is_match)
+            .collect()
+    }
+}
+
+impl fmt::Debug for Descriptor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Descriptor")
+            .field("name", &self.name())
+            .field("kind", &self.kind())
+            .field("default", &self.default())
+            .field("min", &self.min())
+            .field("max", &self.max())
+            .field("unit", &self.unit())
+            .field("flags", &self.flags())
+            .finish()
+    }
+}
+
+/// Iterator over the options of an object and its children, or of a class.
+///
+/// Named constants are skipped, they are available through
+/// [`Descriptor::constants`].
+pub struct Options<'a> {
+    // Objects, i.e. pointers to structs whose first field is an AVClass
+    // pointer. A class is iterated through a pointer to `_class`, boxed so
+    // that its address stays the same.
+    objects: Vec<*const c_void>,
+    _class: Option<Box<*const AVClass>>,
+
+    index: usize,
+    current: *const AVOption,
+    constants: bool,
+
+    _marker: PhantomData<&'a AVOption>,
+}
+
+impl<'a> Options<'a> {
+    /// Iterates over the options of `object` and of all its children.
+    pub(crate) unsafe fn for_object(object: *const c_void) -> Self {
+        let mut objects = Vec::new();
+
+        if !object.is_null() && !(*(object as *const *const AVClass)).is_null() {
+            collect_children(object as *mut c_void, &mut objects);
+        }
+
+        Options {
+            objects,
+            _class: None,
+
+            index: 0,
+            current: ptr::null(),
+            constants: false,
+
+            _marker: PhantomData,
+        }
+    }
+
+    fn for_class(class: *const AVClass) -> Self {
+        let class = Box::new(class);
+
+        Options {
+            objects: vec![&*class as *const *const AVClass as *const c_void],
+            _class: Some(class),
+
+            index: 0,
+            current: ptr::null(),
+            constants: false,
+
+            _marker: PhantomData,
+        }
+    }
+
+    fn with_constants(mut self) -> Self {
+        self.constants = true;
+        self
+    }
+}
+
+unsafe fn collect_children(object: *mut c_void, objects: &mut Vec<*const c_void>) {
+    objects.push(object);
+
+    let mut child = ptr::null_mut();
+
+    loop {
+        child = av_opt_child_next(object, child);
+
+        if child.is_null() {
+            break;
+        }
+
+        collect_children(child, objects);
+    }
+}
+
+impl<'a> Iterator for Options<'a> {
+    type Item = Descriptor<'a>;
+
+    fn next(&mut self) -> Option<Descriptor<'a>> {
+        loop {
+            let object = *self.objects.get(self.index)?;
+
+            unsafe {
+                let option = av_opt_next(object, self.current);
+
+                if option.is_null() {
+                    self.index += 1;
+                    self.current = ptr::null();
+                    continue;
+                }
+
+                self.current = option;
+
+                if (*option).type_ == AVOptionType::CONST && !self.constants {
+                    continue;
+                }
+
+                return Some(Descriptor {
+                    option: &*option,
+                    class: *(object as *const *const AVClass),
+                    object: if self._class.is_some() {
+                        ptr::null()
+                    } else {
+                        self.objects[0]
+                    },
+                });
+            }
+        }
+    }
+}
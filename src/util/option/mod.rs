@@ -1,8 +1,11 @@
 mod traits;
 pub use self::traits::{Gettable, Iterable, Settable};
 
+mod descriptor;
+pub use self::descriptor::{ChildClasses, Class, Descriptor, Flags, Options, Value};
+
 use crate::ffi::*;
-use libc::c_uint;
+use libc::{c_int, c_uint};
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
 
@@ -48,6 +51,29 @@ bitflags::bitflags! {
     }
 }
 
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+    pub struct SerializeFlags: c_int {
+        /// Only serialize options which are not set to their default value.
+        const SKIP_DEFAULTS   = AV_OPT_SERIALIZE_SKIP_DEFAULTS as c_int;
+        /// Only serialize options whose flags are exactly the requested ones.
+        const OPT_FLAGS_EXACT = AV_OPT_SERIALIZE_OPT_FLAGS_EXACT as c_int;
+        /// Serialize the options of the child objects as well.
+        ///
+        /// Before FFmpeg 7.0, the children are walked by
+        /// [`Gettable::serialize`] itself.
+        #[cfg(feature = "ffmpeg_7_0")]
+        const SEARCH_CHILDREN = AV_OPT_SERIALIZE_SEARCH_CHILDREN as c_int;
+        /// Serialize the options of the child objects as well.
+        ///
+        /// Before FFmpeg 7.0, the children are walked by
+        /// [`Gettable::serialize`] itself.
+        #[cfg(not(feature = "ffmpeg_7_0"))]
+        const SEARCH_CHILDREN = 0x04;
+    }
+}
+
 impl Default for Type {
     fn default() -> Self {
         Self::empty()
@@ -1,11 +1,14 @@
 //! NOTE: this will be much better once specialization comes
 
 use std::ffi::CString;
+use std::ptr;
 
+use super::{Options, SerializeFlags};
 use crate::ffi::*;
 use crate::util::format;
-use crate::{AsMutPtr, AsPtr, Error, Rational};
-use libc::c_int;
+use crate::utils;
+use crate::{AsMutPtr, AsPtr, ChannelLayout, Dictionary, Error, Rational};
+use libc::{c_char, c_int, c_void};
 
 #[cfg(not(feature = "ffmpeg_7_0"))]
 use crate::ChannelLayoutMask;
@@ -140,8 +143,295 @@ pub trait Settable<T>: AsPtr<T> + AsMutPtr<T> {
             ))
         }
     }
+
+    /// Sets options from a string like `preset=slow:crf=20`, with the given
+    /// separators between keys and values and between pairs.
+    ///
+    /// Child objects are searched as well, so private options can be set on
+    /// a codec context.
+    fn set_from_string(
+        &mut self,
+        options: &str,
+        key_val_sep: &str,
+        pairs_sep: &str,
+    ) -> Result<(), Error> {
+        let options = CString::new(options).unwrap();
+        let key_val_sep = CString::new(key_val_sep).unwrap();
+        let pairs_sep = CString::new(pairs_sep).unwrap();
+
+        unsafe {
+            let mut cursor = options.as_ptr();
+
+            while *cursor != 0 {
+                let mut key = ptr::null_mut();
+                let mut value = ptr::null_mut();
+
+                check!(av_opt_get_key_value(
+                    &mut cursor,
+                    key_val_sep.as_ptr(),
+                    pairs_sep.as_ptr(),
+                    0,
+                    &mut key,
+                    &mut value
+                ))?;
+
+                let ret = av_opt_set(
+                    self.as_mut_ptr() as *mut _,
+                    key,
+                    value,
+                    AV_OPT_SEARCH_CHILDREN,
+                );
+
+                av_free(key as *mut c_void);
+                av_free(value as *mut c_void);
+
+                check!(ret)?;
+
+                // Skip the pairs separator.
+                if *cursor != 0 {
+                    cursor = cursor.add(1);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets all options of `options`, returning the entries which are not
+    /// options of the object or of its children.
+    fn set_dict(&mut self, options: Dictionary) -> Result<Dictionary, Error> {
+        unsafe {
+            let mut ptr = options.into_raw();
+
+            let ret = av_opt_set_dict2(
+                self.as_mut_ptr() as *mut _,
+                &mut ptr,
+                AV_OPT_SEARCH_CHILDREN,
+            );
+            let remaining = Dictionary::from_raw(ptr);
+
+            check!(ret).map(|_| remaining)
+        }
+    }
+}
+
+pub trait Gettable<T>: AsPtr<T> + AsMutPtr<T> {
+    /// Any option formatted as a string.
+    fn get_str(&self, name: &str) -> Result<String, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = ptr::null_mut();
+
+            check!(av_opt_get(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))?;
+
+            if value.is_null() {
+                return Ok(String::new());
+            }
+
+            let string = utils::str_from_c_ptr(value as *const _).to_owned();
+            av_free(value as *mut c_void);
+
+            Ok(string)
+        }
+    }
+
+    fn get_int(&self, name: &str) -> Result<i64, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = 0;
+
+            check!(av_opt_get_int(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))
+            .map(|_| value)
+        }
+    }
+
+    fn get_double(&self, name: &str) -> Result<f64, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = 0.0;
+
+            check!(av_opt_get_double(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))
+            .map(|_| value)
+        }
+    }
+
+    fn get_rational(&self, name: &str) -> Result<Rational, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = AVRational { num: 0, den: 1 };
+
+            check!(av_opt_get_q(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))
+            .map(|_| Rational::from(value))
+        }
+    }
+
+    /// Width and height of an image size option.
+    fn get_image_size(&self, name: &str) -> Result<(u32, u32), Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut w = 0;
+            let mut h = 0;
+
+            check!(av_opt_get_image_size(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut w,
+                &mut h
+            ))
+            .map(|_| (w as u32, h as u32))
+        }
+    }
+
+    fn get_pixel_format(&self, name: &str) -> Result<format::Pixel, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = AVPixelFormat::NONE;
+
+            check!(av_opt_get_pixel_fmt(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))
+            .map(|_| format::Pixel::from(value))
+        }
+    }
+
+    fn get_sample_format(&self, name: &str) -> Result<format::Sample, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = AVSampleFormat::NONE;
+
+            check!(av_opt_get_sample_fmt(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))
+            .map(|_| format::Sample::from(value))
+        }
+    }
+
+    fn get_channel_layout(&self, name: &str) -> Result<ChannelLayout<'static>, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = AVChannelLayout::empty();
+
+            check!(av_opt_get_chlayout(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))
+            .map(|_| ChannelLayout::from(value))
+        }
+    }
+
+    /// A copy of a dictionary option.
+    fn get_dict(&self, name: &str) -> Result<Dictionary, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = ptr::null_mut();
+
+            check!(av_opt_get_dict_val(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))
+            .map(|_| Dictionary::from_raw(value))
+        }
+    }
+
+    /// Formats the options as a string like `preset=slow:crf=20`, which can
+    /// be applied again with [`Settable::set_from_string`].
+    fn serialize(&self, flags: SerializeFlags) -> Result<String, Error> {
+        unsafe { serialize(self.as_ptr() as *mut c_void, flags) }
+    }
+}
+
+#[cfg(feature = "ffmpeg_7_0")]
+unsafe fn serialize(object: *mut c_void, flags: SerializeFlags) -> Result<String, Error> {
+    serialize_object(object, flags)
+}
+
+// av_opt_serialize ignores the children before FFmpeg 7.0.
+#[cfg(not(feature = "ffmpeg_7_0"))]
+unsafe fn serialize(object: *mut c_void, flags: SerializeFlags) -> Result<String, Error> {
+    let mut string = serialize_object(object, flags - SerializeFlags::SEARCH_CHILDREN)?;
+
+    if !flags.contains(SerializeFlags::SEARCH_CHILDREN) {
+        return Ok(string);
+    }
+
+    let mut child = ptr::null_mut();
+
+    loop {
+        child = av_opt_child_next(object, child);
+
+        if child.is_null() {
+            return Ok(string);
+        }
+
+        let options = serialize(child, flags)?;
+
+        if !options.is_empty() {
+            if !string.is_empty() {
+                string.push(':');
+            }
+
+            string.push_str(&options);
+        }
+    }
 }
 
-pub trait Gettable<T>: AsPtr<T> + AsMutPtr<T> {}
+unsafe fn serialize_object(object: *mut c_void, flags: SerializeFlags) -> Result<String, Error> {
+    let mut buffer = ptr::null_mut();
+
+    check!(av_opt_serialize(
+        object,
+        0,
+        flags.bits(),
+        &mut buffer,
+        b'=' as c_char,
+        b':' as c_char
+    ))?;
 
-pub trait Iterable<T>: AsPtr<T> + AsMutPtr<T> {}
+    if buffer.is_null() {
+        return Ok(String::new());
+    }
+
+    let string = utils::str_from_c_ptr(buffer).to_owned();
+    av_free(buffer as *mut c_void);
+
+    Ok(string)
+}
+
+pub trait Iterable<T>: AsPtr<T> + AsMutPtr<T> {
+    /// Options of the object and of its children, like the private options
+    /// of a codec or muxer.
+    fn options(&self) -> Options<'_> {
+        unsafe { Options::for_object(self.as_ptr() as *const c_void) }
+    }
+}